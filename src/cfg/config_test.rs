@@ -0,0 +1,48 @@
+use std::collections::HashMap;
+use std::path::Path;
+use crate::cfg::{AppConfig, Config};
+
+fn config() -> Config {
+    let mut apps = HashMap::new();
+    apps.insert("gateway".to_string(), AppConfig { path: Some("apps/gateway".to_string()), ..Default::default() });
+    apps.insert("plugin".to_string(), AppConfig { path: Some("./apps/gateway/plugin/".to_string()), ..Default::default() });
+    apps.insert("api".to_string(), AppConfig { path: Some("apps/api".to_string()), ..Default::default() });
+    apps.insert("docs".to_string(), AppConfig::default());
+
+    Config { apps, ..Default::default() }
+}
+
+#[test]
+fn app_in_dir_should_find_the_app_containing_the_directory() {
+    assert_eq!(config().app_in_dir(Path::new("apps/api")), Some("api".to_string()));
+    assert_eq!(config().app_in_dir(Path::new("apps/gateway/src/routes")), Some("gateway".to_string()));
+}
+
+#[test]
+fn app_in_dir_should_prefer_the_most_nested_app() {
+    assert_eq!(config().app_in_dir(Path::new("apps/gateway/plugin/src")), Some("plugin".to_string()));
+}
+
+#[test]
+fn app_in_dir_outside_of_app_paths_should_find_nothing() {
+    assert_eq!(config().app_in_dir(Path::new("")), None);
+    assert_eq!(config().app_in_dir(Path::new("apps")), None);
+    assert_eq!(config().app_in_dir(Path::new("apps/gateway-v2")), None);
+}
+
+#[test]
+fn repo_root_should_be_the_work_tree_containing_the_directory() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path().canonicalize().unwrap();
+    git2::Repository::init(&root).unwrap();
+    std::fs::create_dir_all(root.join("apps/api")).unwrap();
+
+    assert_eq!(Config::repo_root(&root.join("apps/api")).canonicalize().unwrap(), root);
+}
+
+#[test]
+fn repo_root_outside_of_a_repo_should_be_the_directory_itself() {
+    let dir = tempfile::tempdir().unwrap();
+
+    assert_eq!(Config::repo_root(dir.path()), dir.path());
+}
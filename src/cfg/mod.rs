@@ -1,37 +1,173 @@
 use std::collections::HashMap;
 use std::env;
-use std::path::Path;
-use config::{Config as Cfg, ConfigError, Source, ValueKind};
+use std::path::{Path, PathBuf};
+use config::{Config as Cfg, ConfigError, Source, Value, ValueKind};
+use semver::Version;
+use regex::Regex;
+use crate::commands::shell::git::{GitProvider, Tag, DEFAULT_REMOTE};
+use crate::commands::shell::{FetchMode, GitBackend};
+use crate::commands::template::ReleaseTemplate;
+use crate::commands::version_file::VersionFileFormat;
 
-#[derive(Debug, Clone)]
+#[cfg(test)]
+mod config_test;
+
+/// Top level tables which are not application configs
+const RESERVED_KEYS: [&str; 5] = ["github", "gitlab", "bitbucket", "gitea", "providers"];
+
+/// Format of the tags, unless `format` is configured
+pub(crate) const DEFAULT_FORMAT: &str = "{app_name}/v{version}";
+
+/// Version of an application without any tags, unless `initial_version` is configured
+const DEFAULT_INITIAL_VERSION: Version = Version::new(0, 1, 0);
+
+/// Name of a release, unless `release_name` is configured
+const DEFAULT_RELEASE_NAME: &str = "{app_name} - v{version}";
+
+/// Message of the commit updating the changelog and version files, unless `commit_message` is configured
+const DEFAULT_COMMIT_MESSAGE: &str = "chore(release): {app_name} v{version}";
+
+/// File the changelog of an application is kept in, inside its path
+const CHANGELOG_FILE: &str = "CHANGELOG.md";
+
+#[derive(Debug, Clone, Default)]
 pub struct AppConfig {
-    pub path: Option<String>
+    pub path: Option<String>,
+    pub initial_version: Option<Version>,
+    pub changelog: Option<bool>,
+    pub version_files: Vec<VersionFile>,
+    pub release_template: Option<ReleaseTemplate>,
+    pub release_name: Option<String>
+}
+
+/// File holding the version of an application, updated by `bump`
+#[derive(Debug, Clone)]
+pub struct VersionFile {
+    /// Path relative to the root of the repo
+    pub path: String,
+    pub format: VersionFileFormat
 }
 
+/// Git provider of a self-hosted instance, configured in the `[providers]` table by hostname
 #[derive(Debug, Clone)]
+pub struct ProviderConfig {
+    pub provider: GitProvider,
+    pub api_url: Option<String>
+}
+
+#[derive(Debug, Clone, Default)]
 pub struct Config {
     pub format: String,
     pub debug: bool,
+    pub push: bool,
+    pub git_backend: GitBackend,
+    pub fetch: FetchMode,
+    pub remote: String,
+    pub initial_version: Option<Version>,
+    pub changelog: bool,
+    pub commit_message: Option<String>,
+    pub release_template: Option<ReleaseTemplate>,
+    pub release_name: Option<String>,
     pub gh_token: Option<String>,
-    pub apps: HashMap<String, AppConfig>
+    pub gh_tokens: HashMap<String, String>,
+    pub gh_api_url: Option<String>,
+    pub gl_token: Option<String>,
+    pub gl_api_url: Option<String>,
+    pub bb_token: Option<String>,
+    pub bb_username: Option<String>,
+    pub bb_api_url: Option<String>,
+    pub gt_token: Option<String>,
+    pub providers: HashMap<String, ProviderConfig>,
+    pub apps: HashMap<String, AppConfig>,
+    /// Root of the work tree, app paths, version files and templates are relative to it
+    pub root: PathBuf,
+    /// Directory vemo was run in, relative to the root
+    pub work_dir: PathBuf
 }
 
 impl Config {
     pub fn init() -> Result<Config, ConfigError> {
-       let settings = Config::read_config()?;
+        let current_dir = env::current_dir().map_err(|_| {
+            ConfigError::Message(String::from("Failed to open current directory."))
+        })?;
+        let root = Self::repo_root(&current_dir);
+        let work_dir = Self::relative_dir(&root, &current_dir);
+
+        let settings = Config::read_config(&root)?;
 
         let format = settings.get_string("format")
-            .unwrap_or(String::from("{app_name}/v{version}"));
+            .unwrap_or(String::from(DEFAULT_FORMAT));
 
         let debug = settings.get_bool("debug").unwrap_or(false);
-        let gh_token = match settings.get_string("github.token") {
-            Ok(token) => Some(token),
-            _ => None
-        };
+        let push = settings.get_bool("push").unwrap_or(true);
+        let git_backend = settings.get_string("git_backend").ok()
+            .map(|backend| backend.parse::<GitBackend>().map_err(ConfigError::Message))
+            .transpose()?
+            .unwrap_or_default();
+        let fetch = settings.get_string("fetch").ok()
+            .map(|fetch| fetch.parse::<FetchMode>().map_err(ConfigError::Message))
+            .transpose()?
+            .unwrap_or_default();
+        let remote = settings.get_string("remote").unwrap_or_else(|_| DEFAULT_REMOTE.to_string());
+        let initial_version = settings.get_string("initial_version").ok()
+            .map(|v| Self::parse_version("initial_version", &v))
+            .transpose()?;
+        let changelog = settings.get_bool("changelog").unwrap_or(false);
+        let commit_message = settings.get_string("commit_message").ok();
+        let release_template = Self::release_template_config(
+            "", settings.get_string("release_template").ok(), settings.get_string("release_template_file").ok()
+        )?;
+        let release_name = settings.get_string("release_name").ok()
+            .map(|name| Self::release_name_config("release_name", name))
+            .transpose()?;
+        let gh_token = settings.get_string("github.token")
+            .or_else(|_| settings.get_string("github_token"))
+            .ok();
+        let gh_tokens = Self::get_github_tokens(&settings)?;
+        let gh_api_url = settings.get_string("github.api_url").ok();
+        let gl_token = settings.get_string("gitlab.token")
+            .or_else(|_| settings.get_string("gitlab_token"))
+            .ok();
+        let gl_api_url = settings.get_string("gitlab.api_url").ok();
+        let bb_token = settings.get_string("bitbucket.token")
+            .or_else(|_| settings.get_string("bitbucket_token"))
+            .ok();
+        let bb_username = settings.get_string("bitbucket.username").ok();
+        let bb_api_url = settings.get_string("bitbucket.api_url").ok();
+        let gt_token = settings.get_string("gitea.token")
+            .or_else(|_| settings.get_string("gitea_token"))
+            .ok();
 
+        let providers = Self::get_provider_configs(&settings)?;
         let app_configs = Self::get_app_configs(settings)?;
 
-        Ok(Config { format, debug, gh_token, apps: app_configs })
+        Ok(Config {
+            format, debug, push, git_backend, fetch, remote, initial_version, changelog, commit_message, release_template, release_name, gh_token, gh_tokens, gh_api_url, gl_token, gl_api_url, bb_token, bb_username, bb_api_url,
+            gt_token, providers, apps: app_configs, root, work_dir
+        })
+    }
+
+    /// Root of the work tree containing a directory, the directory itself when it is not in a git repo
+    pub(crate) fn repo_root(current_dir: &Path) -> PathBuf {
+        git2::Repository::discover(current_dir).ok()
+            .and_then(|repo| repo.workdir().map(Path::to_path_buf))
+            .unwrap_or_else(|| current_dir.to_path_buf())
+    }
+
+    /// Path of a directory relative to the root, empty when it is outside of the root
+    fn relative_dir(root: &Path, dir: &Path) -> PathBuf {
+        let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+        let dir = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+        dir.strip_prefix(&root).map(Path::to_path_buf).unwrap_or_default()
+    }
+
+    /// Application with a path containing a directory relative to the root, the most nested one if paths overlap
+    pub fn app_in_dir(&self, dir: &Path) -> Option<String> {
+        self.apps.iter()
+            .filter_map(|(name, app)| app.path.as_ref().map(|path| (name, Path::new(path.trim_start_matches("./")))))
+            .filter(|(_, path)| !path.as_os_str().is_empty() && path != &Path::new(".") && dir.starts_with(path))
+            .max_by_key(|(_, path)| path.components().count())
+            .map(|(name, _)| name.clone())
     }
 
     pub fn app_path(&self, app_name: &str) -> Option<String> {
@@ -40,17 +176,77 @@ impl Config {
             .unwrap_or(None)
     }
 
-    fn read_config() -> Result<Cfg, ConfigError> {
-        let current_dir = env::current_dir().map_err(|_| {
-            ConfigError::Message(String::from("Failed to open current directory."))
-        })?;
+    /// Version of the first release of an application, the app setting takes precedence over the global one
+    pub fn initial_version(&self, app_name: &str) -> Version {
+        self.apps.get(app_name)
+            .and_then(|app| app.initial_version.clone())
+            .or_else(|| self.initial_version.clone())
+            .unwrap_or(DEFAULT_INITIAL_VERSION)
+    }
+
+    /// Path of the changelog file of an application, if it is enabled and the application has a path
+    pub fn changelog_path(&self, app_name: &str) -> Option<String> {
+        let enabled = self.apps.get(app_name)
+            .and_then(|app| app.changelog)
+            .unwrap_or(self.changelog);
+        if !enabled {
+            return None
+        }
+
+        self.app_path(app_name)
+            .map(|path| format!("{}/{}", path.trim_end_matches('/'), CHANGELOG_FILE))
+    }
+
+    /// Version files of an application
+    pub fn version_files(&self, app_name: &str) -> Vec<VersionFile> {
+        self.apps.get(app_name)
+            .map(|app| app.version_files.clone())
+            .unwrap_or_default()
+    }
+
+    /// Release body template of an application, the app setting takes precedence over the global one
+    pub fn release_template(&self, app_name: &str) -> Option<ReleaseTemplate> {
+        self.apps.get(app_name)
+            .and_then(|app| app.release_template.clone())
+            .or_else(|| self.release_template.clone())
+    }
+
+    /// Format of the release name of an application, the app setting takes precedence over the global one
+    pub fn release_name(&self, app_name: &str) -> String {
+        self.apps.get(app_name)
+            .and_then(|app| app.release_name.clone())
+            .or_else(|| self.release_name.clone())
+            .unwrap_or_else(|| DEFAULT_RELEASE_NAME.to_string())
+    }
+
+    /// Message of the commit updating the changelog and version files, `{app_name}`, `{version}` and `{tag}` are replaced
+    pub fn commit_message(&self, app_name: &str, version: &Version, tag: &str) -> String {
+        self.commit_message.as_deref()
+            .unwrap_or(DEFAULT_COMMIT_MESSAGE)
+            .replace("{app_name}", app_name)
+            .replace("{version}", &version.to_string())
+            .replace("{tag}", tag)
+    }
+
+    /// Provider configured for a given host in the `[providers]` table or `github.hosts`
+    pub fn provider_config(&self, host: &str) -> Option<&ProviderConfig> {
+        self.providers.get(&host.to_lowercase())
+    }
 
+    /// GitHub token for a given host, `github.tokens` takes precedence over `github.token`
+    pub fn github_token(&self, host: &str) -> Option<String> {
+        self.gh_tokens.get(&host.to_lowercase())
+            .cloned()
+            .or_else(|| self.gh_token.clone())
+    }
+
+    fn read_config(root: &Path) -> Result<Cfg, ConfigError> {
         let home_dir = dirs::home_dir()
             .ok_or(ConfigError::Message(String::from("Failed to get home directory.")))?;
         let home_config = home_dir.join(".config/vemo/config.toml");
 
-        let config_path = format!("{}/.vemo.toml", current_dir.display());
-        let config_file = Path::new(&config_path);
+        let config_path = root.join(".vemo.toml");
+        let config_file = config_path.as_path();
 
         let settings = config::Config::builder();
 
@@ -62,32 +258,166 @@ impl Config {
             settings.add_source(config::File::from(config_file))
         } else { settings };
 
-        return settings
+        settings
             .add_source(config::Environment::with_prefix("VEMO"))
-            .build();
+            .build()
     }
 
     fn get_app_configs(settings: Cfg) -> Result<HashMap<String, AppConfig>, ConfigError> {
         let mut app_configs: HashMap<String, AppConfig> = HashMap::new();
 
         for (key, value) in settings.collect().unwrap() {
-            // If the key is github, then it should not be considered as an app config.
-            if key == "github" {
+            if RESERVED_KEYS.contains(&key.as_str()) {
                 continue;
             }
-            match value.kind {
-                ValueKind::Table(t) => {
-                    let path = t.get("path").map(|v| {
-                        v.clone().into_string().map(Some)
-                    }).unwrap_or(Ok(None));
-
-                    let app_config = AppConfig { path: path? };
-                    app_configs.insert(key, app_config);
-                }
-                _ => { }
-            };
+            if let ValueKind::Table(t) = value.kind {
+                let path = t.get("path").map(|v| {
+                    v.clone().into_string().map(Some)
+                }).unwrap_or(Ok(None));
+                let initial_version = t.get("initial_version")
+                    .map(|v| Self::app_version(&key, v))
+                    .transpose()?;
+
+                let changelog = t.get("changelog")
+                    .map(|v| v.clone().into_bool())
+                    .transpose()?;
+
+                let version_files = match t.get("version_files") {
+                    Some(v) => Self::version_files_config(&key, v)?,
+                    None => vec![]
+                };
+
+                let field = |name: &str| t.get(name).map(|v| v.clone().into_string()).transpose();
+                let release_template = Self::release_template_config(
+                    &format!("{}.", key), field("release_template")?, field("release_template_file")?
+                )?;
+
+                let release_name = field("release_name")?
+                    .map(|name| Self::release_name_config(&format!("{}.release_name", key), name))
+                    .transpose()?;
+
+                let app_config = AppConfig {
+                    path: path?, initial_version, changelog, version_files, release_template, release_name
+                };
+                app_configs.insert(key, app_config);
+            }
         }
 
-        return Ok(app_configs)
+        Ok(app_configs)
+    }
+
+    /// Make sure date placeholders of a release name can be rendered
+    fn release_name_config(key: &str, name: String) -> Result<String, ConfigError> {
+        let re = Regex::new(r"\{date:(?P<format>[^}]+)\}").unwrap();
+        let invalid = re.captures_iter(&name)
+            .map(|caps| caps["format"].to_string())
+            .find(|format| !Tag::is_date_format(format));
+
+        match invalid {
+            Some(format) => Err(ConfigError::Message(format!("Invalid date format \"{}\" in \"{}\"", format, key))),
+            None => Ok(name)
+        }
+    }
+
+    /// Template is either inline or in a file, `prefix` is the table of the settings used in errors
+    fn release_template_config(prefix: &str, inline: Option<String>, file: Option<String>) -> Result<Option<ReleaseTemplate>, ConfigError> {
+        match (inline, file) {
+            (Some(_), Some(_)) => Err(ConfigError::Message(format!(
+                "Only one of \"{0}release_template\" and \"{0}release_template_file\" can be set", prefix
+            ))),
+            (Some(template), None) => Ok(Some(ReleaseTemplate::Inline(template))),
+            (None, Some(path)) => Ok(Some(ReleaseTemplate::File(path))),
+            (None, None) => Ok(None)
+        }
+    }
+
+    fn version_files_config(app_name: &str, value: &Value) -> Result<Vec<VersionFile>, ConfigError> {
+        let mut version_files = vec![];
+        for file in value.clone().into_array()? {
+            let t = file.into_table()?;
+            let field = |name: &str| t.get(name).map(|v| v.clone().into_string()).transpose();
+
+            let path = field("path")?
+                .ok_or_else(|| ConfigError::Message(format!("Missing \"path\" of a version file of {}", app_name)))?;
+            let format = field("format")?
+                .ok_or_else(|| ConfigError::Message(format!("Missing \"format\" of version file {} of {}", path, app_name)))?;
+            let format = VersionFileFormat::parse(&format, field("pattern")?)
+                .map_err(|e| ConfigError::Message(format!("Invalid version file {} of {}: {}", path, app_name, e)))?;
+
+            version_files.push(VersionFile { path, format });
+        }
+
+        Ok(version_files)
+    }
+
+    fn app_version(app_name: &str, value: &Value) -> Result<Version, ConfigError> {
+        let version = value.clone().into_string()?;
+        Self::parse_version(&format!("{}.initial_version", app_name), &version)
+    }
+
+    fn parse_version(key: &str, version: &str) -> Result<Version, ConfigError> {
+        Version::parse(version)
+            .map_err(|e| ConfigError::Message(format!("Invalid version \"{}\" in \"{}\": {}", version, key, e)))
+    }
+
+    fn get_github_tokens(settings: &Cfg) -> Result<HashMap<String, String>, ConfigError> {
+        let table = match settings.get_table("github.tokens") {
+            Ok(table) => table,
+            Err(ConfigError::NotFound(_)) => return Ok(HashMap::new()),
+            Err(e) => return Err(e)
+        };
+
+        table.into_iter()
+            .map(|(host, token)| token.into_string().map(|token| (host.to_lowercase(), token)))
+            .collect()
+    }
+
+    fn get_provider_configs(settings: &Cfg) -> Result<HashMap<String, ProviderConfig>, ConfigError> {
+        let mut provider_configs: HashMap<String, ProviderConfig> = HashMap::new();
+
+        // GitHub Enterprise hosts, listed in `github.hosts`
+        let github_hosts = match settings.get_array("github.hosts") {
+            Ok(hosts) => hosts,
+            Err(ConfigError::NotFound(_)) => vec![],
+            Err(e) => return Err(e)
+        };
+        for host in github_hosts {
+            let provider_config = ProviderConfig { provider: GitProvider::Github, api_url: None };
+            provider_configs.insert(host.into_string()?.to_lowercase(), provider_config);
+        }
+
+        let table = match settings.get_table("providers") {
+            Ok(table) => table,
+            Err(ConfigError::NotFound(_)) => return Ok(provider_configs),
+            Err(e) => return Err(e)
+        };
+
+        for (host, value) in table {
+            let t = value.into_table()?;
+            let provider = t.get("type")
+                .ok_or_else(|| ConfigError::Message(format!("Missing \"type\" of provider {}", host)))?
+                .clone()
+                .into_string()?
+                .parse::<GitProvider>()
+                .map_err(ConfigError::Message)?;
+            let api_url = t.get("api_url").map(|v| {
+                v.clone().into_string().map(Some)
+            }).unwrap_or(Ok(None))?;
+
+            provider_configs.insert(host.to_lowercase(), ProviderConfig { provider, api_url });
+        }
+
+        Ok(provider_configs)
+    }
+}
+
+#[cfg(test)]
+impl Config {
+    /// Config with the default tag format and the `gateway` application in the `gateway` directory
+    pub(crate) fn fixture() -> Config {
+        let mut apps = HashMap::new();
+        apps.insert("gateway".to_string(), AppConfig { path: Some("gateway".to_string()), ..Default::default() });
+
+        Config { format: DEFAULT_FORMAT.to_string(), apps, ..Default::default() }
     }
 }
@@ -1,76 +1,424 @@
-use std::io::stdin;
-use semver::Version;
+use std::fs;
+use std::io::{ErrorKind, IsTerminal, stdin};
+use chrono::Local;
+use clap::Args;
 use crate::cfg::Config;
 use crate::commands::Component;
+use crate::commands::changed::find_changed_apps;
+use crate::commands::changelog;
+use crate::commands::changelog::{changelog_section, insert_section, release_notes, ReleaseNotes};
 use crate::commands::error::CommandError;
-use crate::commands::shell::git::{Git, Tag};
+use crate::commands::shell::git::{Commit, Git, Tag};
 use colored::Colorize;
-use log::debug;
+use log::{debug, warn};
 use crate::git::GitClient;
 
-pub fn run(config: Config, name: &String, component: &Component, git_client: Box<dyn GitClient>) -> Result<(), CommandError>  {
-    let format = config.format.clone();
-    let git = Git::init(format.clone());
+/// Bump the version of one or more applications and create a release for each of them.
+#[derive(Args)]
+pub struct BumpArgs {
+    /// Names of applications inside a monorepo.
+    ///
+    /// If not provided, the application with a configured "path" containing the current directory is bumped.
+    pub names: Vec<String>,
 
-    let default_version = Version::parse("0.1.0").unwrap();
+    /// Bump all applications with unreleased changes (see `vemo changed`)
+    #[clap(long, conflicts_with = "names")]
+    pub changed: bool,
 
-    let (latest_tag, new_tag) = match git.find_latest_tag(name)? {
-        None => {
-            debug!("Version of {} not found, new tag with default version ({}) version will be created", name, default_version);
-            (None, Tag::new_with_format(&format, name, default_version))
+    /// Version component which will be bumped
+    ///
+    /// If not provided, the component is inferred from Conventional Commits made in the
+    /// application path since the latest version: major for breaking changes, minor for
+    /// `feat` and patch for `fix` or `perf`.
+    #[clap(short, long, arg_enum)]
+    pub component: Option<Component>,
+
+    /// Create a pre-release with a given identifier (e.g. alpha, beta, rc).
+    ///
+    /// If the latest version is already a pre-release with the same identifier,
+    /// only the pre-release number is incremented.
+    #[clap(long, value_name = "IDENTIFIER")]
+    pub pre: Option<String>,
+
+    /// Promote the latest pre-release to a release version (e.g. 1.3.0-rc.2 -> 1.3.0)
+    #[clap(long, conflicts_with = "pre")]
+    pub promote: bool,
+
+    /// Create the releases without asking for confirmation.
+    ///
+    /// Confirmation is also skipped when stdin is not a terminal (e.g. in CI).
+    #[clap(short, long)]
+    pub yes: bool,
+
+    /// Print the computed tags, release names and bodies without creating anything
+    #[clap(long)]
+    pub dry_run: bool
+}
+
+/// Release of a single application, computed before anything is created
+pub(crate) struct Release {
+    pub(crate) name: String,
+    pub(crate) tag: Tag,
+    pub(crate) body: String,
+    /// Files committed before the tag is created, e.g. the changelog or version files
+    pub(crate) files: Vec<FileChange>,
+    pub(crate) commit_message: String
+}
+
+/// New content of a file in the repo
+pub(crate) struct FileChange {
+    pub(crate) path: String,
+    pub(crate) content: String
+}
+
+impl Release {
+    /// Release of a new tag, with the commits made since `from` listed in the body
+    ///
+    /// The changelog and version files of the application are read and updated in memory, so that
+    /// a missing file or version fails before anything is created. The changelog is only updated
+    /// for stable versions with listed changes.
+    pub(crate) fn new(config: &Config, git: &Git, app_name: &str, from: &Option<Tag>, tag: Tag, commits: Vec<Commit>) -> Result<Release, CommandError> {
+        let notes = ReleaseNotes {
+            app_name, from: from.as_ref(), tag: Some(&tag), to: &tag.formatted(), commits: &commits
+        };
+        let body = release_notes(config, git, &notes)?;
+        let date = Local::today().naive_local();
+        let name = tag.release_name(&config.release_name(app_name), date);
+
+        let mut files = vec![];
+        // Pre-releases are left out of the changelog, their changes are listed once the version is promoted
+        if let Some(path) = config.changelog_path(app_name).filter(|_| tag.version.pre.is_empty()) {
+            if let Some(section) = changelog_section(&tag.version, date, &commits) {
+                let content = insert_section(read_file(&path)?.as_deref(), &section);
+                files.push(FileChange { path, content });
+            }
         }
-        Some(tag) => (Some(tag.clone()), tag.bump(component))
+        for version_file in config.version_files(app_name) {
+            let current = read_file(&version_file.path)?.ok_or_else(|| CommandError::FileError(
+                format!("Version file {} of {} not found", version_file.path, app_name)
+            ))?;
+            let content = version_file.format.update(&current, &tag.version).ok_or_else(|| CommandError::FileError(
+                format!("Version not found in {} ({} format)", version_file.path, version_file.format)
+            ))?;
+            files.push(FileChange { path: version_file.path, content });
+        }
+
+        let commit_message = config.commit_message(app_name, &tag.version, &tag.formatted());
+
+        Ok(Release { name, tag, body, files, commit_message })
+    }
+}
+
+pub fn run(config: Config, args: &BumpArgs, git_client: Option<Box<dyn GitClient>>) -> Result<(), CommandError>  {
+    let git = Git::init(&config)?;
+
+    let names = app_names(&config, &git, args)?;
+    if names.is_empty() {
+        println!("{}", "No applications with unreleased changes found, nothing to bump.".yellow());
+        return Ok(())
+    }
+
+    let releases = names.iter()
+        .map(|name| prepare(&config, &git, name, args))
+        .collect::<Result<Vec<Release>, CommandError>>()?;
+
+    release(&git, config.push, git_client, releases, args.yes, args.dry_run, stdin().is_terminal())
+}
+
+/// Names of the applications to bump, each name is listed once
+pub(crate) fn app_names(config: &Config, git: &Git, args: &BumpArgs) -> Result<Vec<String>, CommandError> {
+    let names = if args.changed {
+        changed_app_names(config, git, args)?
+    } else if args.names.is_empty() {
+        let name = config.app_in_dir(&config.work_dir).ok_or_else(|| CommandError::UsageError(
+            "No application has a path containing the current directory, pass the name of the application".to_string()
+        ))?;
+        debug!("Bumping {}, its path contains the current directory", name);
+        vec![name]
+    } else {
+        args.names.clone()
     };
 
-    let commits = config.app_path(name.as_str()).map(|path| {
-        git.get_commits(latest_tag.clone(), path.as_str())
-    }).unwrap_or(Ok(vec![]));
+    let mut unique: Vec<String> = vec![];
+    for name in names {
+        if !unique.contains(&name) {
+            unique.push(name);
+        }
+    }
 
-    let mut body = String::from("## What's Changed\n\n");
+    Ok(unique)
+}
 
-    for commit in commits? {
-        body.push_str(&format!("* {} by {}\n", commit.message, commit.author.email));
+/// Names of changed applications, apps without releasable changes are skipped unless the component is given
+fn changed_app_names(config: &Config, git: &Git, args: &BumpArgs) -> Result<Vec<String>, CommandError> {
+    let mut names = vec![];
+    for app in find_changed_apps(config, git)? {
+        if app.component.is_none() && args.component.is_none() && !args.promote {
+            println!("Skipping {}, no releasable changes since {}",
+                     app.name.bold(), app.latest_tag.map(|t| t.to_string()).unwrap_or_default());
+            continue
+        }
+        names.push(app.name);
     }
 
-    let repo_url = git.get_repo_info()?.git_url;
+    Ok(names)
+}
+
+/// Compute the new tag and release body of an application
+pub(crate) fn prepare(config: &Config, git: &Git, name: &String, args: &BumpArgs) -> Result<Release, CommandError> {
+    let format = config.format.clone();
+
+    let initial_version = config.initial_version(name);
+
+    let latest_tag = git.find_latest_tag(name)?;
+
+    let commits = app_commits(config, git, name, latest_tag.as_ref())?;
+
+    let new_tag = match latest_tag.clone() {
+        None if args.promote => {
+            return Err(CommandError::VersionError(format!("Version of {} not found, there is nothing to promote", name)))
+        }
+        None => {
+            debug!("Version of {} not found, new tag with initial version ({}) will be created", name, initial_version);
+            let tag = Tag::new_with_format(&format, name, initial_version);
+            match &args.pre {
+                Some(identifier) => tag.with_pre_release(identifier)?,
+                None => tag
+            }
+        }
+        Some(tag) if args.promote => tag.promote()?,
+        Some(tag) => {
+            let component = match args.component {
+                Some(component) => component,
+                None => infer_component(&tag, &commits)?
+            };
+            next_tag(tag, &component, &args.pre)?
+        }
+    };
+
+    let from = notes_base(git, name, &latest_tag, &new_tag)?;
+    let commits = if from == latest_tag {
+        commits
+    } else {
+        app_commits(config, git, name, from.as_ref())?
+    };
+
+    Release::new(config, git, name, &from, new_tag, commits.unwrap_or_default())
+}
 
-    if latest_tag.is_some() {
-        body.push_str(&format!("\n\n**Full Changelog**: {}/compare/{}...{}", repo_url, &latest_tag.unwrap(), new_tag));
+/// Commits of an application made since a tag, without release commits, `None` when its "path" is not configured
+pub(crate) fn app_commits(config: &Config, git: &Git, name: &str, from: Option<&Tag>) -> Result<Option<Vec<Commit>>, CommandError> {
+    config.app_path(name)
+        .map(|path| changelog::app_commits(config, git, name, from, "HEAD", Some(path)))
+        .transpose()
+}
 
+/// Version the release notes of a new tag start from.
+///
+/// Pre-releases list the changes since the latest version, stable versions all changes since the
+/// latest stable version, including those already released in pre-releases.
+pub(crate) fn notes_base(git: &Git, name: &str, latest_tag: &Option<Tag>, new_tag: &Tag) -> Result<Option<Tag>, CommandError> {
+    match latest_tag {
+        Some(latest) if new_tag.version.pre.is_empty() && !latest.version.pre.is_empty() => git.find_latest_stable_tag(name),
+        _ => Ok(latest_tag.clone())
     }
+}
 
+/// Bumping a pending pre-release to the next release would skip the version it leads to,
+/// so it has to be promoted or followed by another pre-release instead.
+fn next_tag(tag: Tag, component: &Component, pre: &Option<String>) -> Result<Tag, CommandError> {
+    match pre {
+        Some(identifier) => tag.bump_pre_release(component, identifier),
+        None if !tag.version.pre.is_empty() => Err(CommandError::VersionError(format!(
+            "Latest version of {} is the pre-release {}, use --promote to release it or --pre to create another pre-release",
+            tag.app_name, tag.version
+        ))),
+        None => Ok(tag.bump(component))
+    }
+}
+
+/// Pick the most significant component implied by the commits made since the latest tag.
+fn infer_component(latest_tag: &Tag, commits: &Option<Vec<Commit>>) -> Result<Component, CommandError> {
+    let commits = commits.as_ref().ok_or_else(|| CommandError::VersionError(format!(
+        "Unable to infer the version component, \"path\" is not configured for {}. Use --component instead",
+        latest_tag.app_name
+    )))?;
 
-    // let body = edit::edit(template).unwrap(); // TODO: handle error
-    // let body = String::from("This is\na multi line\n string ");
-    let release_name = format!("{} - v{}", &name, &new_tag.version);
+    let component = Component::from_commits(commits)
+        .ok_or_else(|| CommandError::VersionError(format!(
+            "No releasable changes found for {} since {}, nothing to bump", latest_tag.app_name, latest_tag
+        )))?;
+    debug!("Inferred {:?} version component from {} commits", component, commits.len());
 
-    release(git_client, release_name, new_tag, body)
+    Ok(component)
 }
 
-fn release(git_client: Box<dyn GitClient>, name: String, new_tag: Tag, body: String) -> Result<(), CommandError> {
-    println!("  {} {}", "name:".bold(), &name.bright_green().bold());
-    println!("  {}  {}", "tag:".bold(), &new_tag.formatted().bright_green().bold());
-    println!("  {}", "body:".bold());
-    for line in body.split("\n").into_iter() {
-        println!("    {}", line);
+/// Show the releases and create them once confirmed, the confirmation is skipped with `yes` or when not `interactive`
+/// (stdin is not a terminal)
+pub(crate) fn release(git: &Git, push: bool, git_client: Option<Box<dyn GitClient>>, releases: Vec<Release>, yes: bool, dry_run: bool, interactive: bool) -> Result<(), CommandError> {
+    for release in &releases {
+        println!("  {} {}", "name:".bold(), &release.name.bright_green().bold());
+        println!("  {}  {}", "tag:".bold(), &release.tag.formatted().bright_green().bold());
+        if !release.files.is_empty() {
+            let paths: Vec<&str> = release.files.iter().map(|file| file.path.as_str()).collect();
+            println!("  {} {}", "files:".bold(), paths.join(", "));
+        }
+        println!("  {}", "body:".bold());
+        for line in release.body.split('\n') {
+            println!("    {}", line);
+        }
+        println!();
+    }
+    if git_client.is_none() {
+        println!("  {} {}", "note:".bold(), "releases are not published to a git provider, only tags will be created".yellow());
+    }
+
+    if dry_run {
+        println!("{}", "Dry run, nothing was created".yellow());
+        return Ok(())
+    }
 
+    if yes || !interactive {
+        debug!("Skipping confirmation, --yes is set or stdin is not a terminal");
+        println!("Applying changes");
+        return publish(git, push, git_client, releases)
     }
-    println!("{}", "Are you sure you want to create new release with [y/e/N]:".yellow());
+
+    let question = match releases.len() {
+        1 => "Are you sure you want to create new release with [y/e/N]:".to_string(),
+        n => format!("Are you sure you want to create {} new releases with [y/e/N]:", n)
+    };
+    println!("{}", question.yellow());
     let stdin = stdin();
     let mut s: String = String::new();
-    stdin.read_line(&mut s).unwrap();
-    let s = s.replace("\n", "");
+    stdin.read_line(&mut s)
+        .map_err(|e| CommandError::PromptError(format!("Unable to read the confirmation, {}", e)))?;
+    let s = s.replace('\n', "");
 
     if &s == "y" || &s == "Y" {
         println!("Applying changes");
-        git_client.create_release(name, new_tag, body)?;
+        publish(git, push, git_client, releases)?;
     } else if &s == "e" || &s == "E" {
         println!("Editing changes");
-        let edited = edit::edit(body).unwrap();
-        release(git_client, name, new_tag, edited)?;
+        let edited = releases.into_iter()
+            .map(|release| match edit::edit(&release.body) {
+                Ok(body) => Ok(Release { body, ..release }),
+                Err(e) => Err(CommandError::PromptError(format!("Unable to edit the release of {}, {}", release.tag, e)))
+            })
+            .collect::<Result<Vec<Release>, CommandError>>()?;
+        release(git, push, git_client, edited, yes, dry_run, interactive)?;
     } else {
         println!("Aborting");
     }
 
     Ok(())
 }
+
+/// Create annotated tags, push them and publish releases if a git provider client is available.
+///
+/// Changelogs and version files are committed and all tags are created and pushed before any release is published,
+/// a failure deletes the local tags and reverts the commits made in this run. Providers without
+/// releases (e.g. Bitbucket) create the annotated tags themselves.
+fn publish(git: &Git, push: bool, git_client: Option<Box<dyn GitClient>>, releases: Vec<Release>) -> Result<(), CommandError> {
+    let original_head = git.head_commit()?;
+    let provider_creates_tag = git_client.as_ref().map(|c| c.creates_tag()).unwrap_or(false);
+
+    let mut created: Vec<Tag> = vec![];
+    let mut targets: Vec<String> = vec![];
+    for release in &releases {
+        match prepare_target(git, release, !provider_creates_tag) {
+            Ok(target) => targets.push(target),
+            Err(e) => {
+                rollback(git, &created, &original_head);
+                return Err(e)
+            }
+        }
+        if !provider_creates_tag {
+            created.push(release.tag.clone());
+        }
+    }
+
+    let committed = releases.iter().any(|release| !release.files.is_empty());
+    if push && (committed || !created.is_empty()) {
+        if let Err(e) = git.push_tags(&created, committed) {
+            rollback(git, &created, &original_head);
+            return Err(e)
+        }
+        debug!("Pushed {} tags", created.len());
+    }
+
+    if let Some(git_client) = git_client {
+        let tags: Vec<String> = releases.iter().map(|release| release.tag.formatted()).collect();
+        for (index, (release, target)) in releases.into_iter().zip(targets).enumerate() {
+            if let Err(e) = git_client.create_release(release.name, release.tag, release.body, target) {
+                report_unpublished(&tags[..index], &tags[index..], !provider_creates_tag);
+                return Err(e.into())
+            }
+            println!("Released {}", tags[index].bright_green());
+        }
+    }
+
+    Ok(())
+}
+
+/// Show which releases were published before the git provider failed, nothing is rolled back at this point
+/// because the published releases already point to the tags
+fn report_unpublished(published: &[String], unpublished: &[String], tagged: bool) {
+    let published = match published {
+        [] => "none".to_string(),
+        tags => tags.join(", ")
+    };
+    println!("  {} {}", "released:".bold(), published.bright_green());
+    println!("  {} {}", "not released:".bold(), unpublished.join(", ").yellow());
+    if tagged {
+        println!("{}", "Tags of the unpublished releases were created, publish them in the git provider manually".yellow());
+    }
+}
+
+/// Commit the changed files and create the tag of a release, returns the commit the release points to
+fn prepare_target(git: &Git, release: &Release, create_tag: bool) -> Result<String, CommandError> {
+    if !release.files.is_empty() {
+        for file in &release.files {
+            fs::write(&file.path, &file.content)
+                .map_err(|e| CommandError::FileError(format!("Unable to write {}, {}", file.path, e)))?;
+        }
+        let paths: Vec<String> = release.files.iter().map(|file| file.path.clone()).collect();
+        git.commit_files(&paths, &release.commit_message)?;
+        debug!("Committed {:?} with message \"{}\"", paths, release.commit_message);
+    }
+
+    let target = git.head_commit()?;
+
+    if create_tag {
+        git.create_tag(&release.tag, &release.body)?;
+        debug!("Created tag {}", release.tag);
+    }
+
+    Ok(target)
+}
+
+/// Content of a file in the repo, `None` if it does not exist
+fn read_file(path: &str) -> Result<Option<String>, CommandError> {
+    match fs::read_to_string(path) {
+        Ok(content) => Ok(Some(content)),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(CommandError::FileError(format!("Unable to read {}, {}", path, e)))
+    }
+}
+
+/// Delete local tags and revert commits created during the current run
+fn rollback(git: &Git, tags: &[Tag], original_head: &str) {
+    for tag in tags {
+        match git.delete_tag(tag) {
+            Ok(_) => println!("Rolled back tag {}", tag.formatted().yellow()),
+            Err(e) => warn!("Unable to delete tag {}: {:?}", tag, e)
+        }
+    }
+
+    if git.head_commit().map(|head| head != original_head).unwrap_or(false) {
+        match git.reset(original_head) {
+            Ok(_) => println!("Rolled back commits, HEAD is at {}", original_head.yellow()),
+            Err(e) => warn!("Unable to reset HEAD to {}: {:?}", original_head, e)
+        }
+    }
+}
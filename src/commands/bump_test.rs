@@ -0,0 +1,283 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use mockall::predicate::*;
+use crate::cfg::{Config, VersionFile, DEFAULT_FORMAT};
+use crate::commands::bump::{app_names, prepare, release, BumpArgs, FileChange, Release};
+use crate::commands::Component;
+use crate::commands::version_file::VersionFileFormat;
+use crate::commands::error::CommandError;
+use crate::commands::shell::git::{Commit, Git, Tag};
+use crate::commands::shell::MockGitCli;
+use crate::git::client::error::GitClientError;
+use crate::git::GitClient;
+
+fn release_plan(app_name: &str, version: &str, files: Vec<FileChange>) -> Release {
+    let tag = Tag::fixture(app_name, version);
+    Release {
+        name: tag.formatted(),
+        tag,
+        body: "- fix: handle timeout".to_string(),
+        files,
+        commit_message: format!("chore(release): {} {}", app_name, version)
+    }
+}
+
+fn args() -> BumpArgs {
+    BumpArgs { names: vec![], changed: false, component: None, pre: None, promote: false, yes: false, dry_run: false }
+}
+
+/// Repo with version 0.1.0 of the gateway and two release candidates of 0.1.1
+fn pre_release_mock() -> MockGitCli {
+    let mut mock = MockGitCli::new();
+    mock.expect_get_tags()
+        .returning(|_| Ok("gateway/v0.1.0\ngateway/v0.1.1-rc.1\ngateway/v0.1.1-rc.2".to_string()));
+    mock.expect_get_commits()
+        .with(eq(Some("gateway/v0.1.1-rc.2".to_string())), eq("HEAD"), eq(Some("gateway".to_string())))
+        .returning(|_, _, _| Ok(vec![Commit::fixture("fix: retry failed requests")]));
+    mock.expect_get_commits()
+        .with(eq(Some("gateway/v0.1.0".to_string())), eq("HEAD"), eq(Some("gateway".to_string())))
+        .returning(|_, _, _| Ok(vec![
+            Commit::fixture("fix: retry failed requests"),
+            Commit::fixture("chore(release): gateway v0.1.1-rc.2"),
+            Commit::fixture("feat: add health route"),
+            Commit::fixture("chore(release): gateway v0.1.1-rc.1"),
+            Commit::fixture("fix: handle timeout"),
+        ]));
+    mock.expect_get_config()
+        .returning(|key| Err(CommandError::ShellError(format!("{} not found", key))));
+    mock
+}
+
+#[test]
+fn prepare_promoted_version_should_list_changes_since_latest_stable_version() {
+    let git = Git::new(Box::new(pre_release_mock()), DEFAULT_FORMAT.to_string());
+
+    let release = prepare(&Config::fixture(), &git, &"gateway".to_string(), &BumpArgs { promote: true, ..args() }).unwrap();
+
+    assert_eq!(release.tag.formatted(), "gateway/v0.1.1");
+    assert!(release.body.contains("retry failed requests"));
+    assert!(release.body.contains("add health route"));
+    assert!(release.body.contains("handle timeout"));
+}
+
+#[test]
+fn prepare_promoted_version_with_version_files_should_leave_out_release_commits_of_pre_releases() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("Cargo.toml").to_string_lossy().to_string();
+    std::fs::write(&path, "[package]\nname = \"gateway\"\nversion = \"0.1.1-rc.2\"\n").unwrap();
+    let mut config = Config::fixture();
+    config.apps.get_mut("gateway").unwrap().version_files = vec![VersionFile { path, format: VersionFileFormat::Cargo }];
+    let git = Git::new(Box::new(pre_release_mock()), DEFAULT_FORMAT.to_string());
+
+    let release = prepare(&config, &git, &"gateway".to_string(), &BumpArgs { promote: true, ..args() }).unwrap();
+
+    assert_eq!(release.tag.formatted(), "gateway/v0.1.1");
+    assert!(!release.body.contains("chore(release)"), "{}", release.body);
+    assert!(release.body.contains("add health route"));
+    assert!(release.files[0].content.contains("version = \"0.1.1\""));
+}
+
+#[test]
+fn prepare_pre_release_should_list_changes_since_latest_pre_release() {
+    let git = Git::new(Box::new(pre_release_mock()), DEFAULT_FORMAT.to_string());
+
+    let release = prepare(&Config::fixture(), &git, &"gateway".to_string(), &BumpArgs { pre: Some("rc".to_string()), ..args() }).unwrap();
+
+    assert_eq!(release.tag.formatted(), "gateway/v0.1.1-rc.3");
+    assert!(release.body.contains("retry failed requests"));
+    assert!(!release.body.contains("handle timeout"));
+}
+
+#[test]
+fn prepare_release_after_pre_release_should_ask_to_promote() {
+    let git = Git::new(Box::new(pre_release_mock()), DEFAULT_FORMAT.to_string());
+
+    for component in [Component::Minor, Component::Patch] {
+        let args = BumpArgs { component: Some(component), ..args() };
+        match prepare(&Config::fixture(), &git, &"gateway".to_string(), &args) {
+            Err(CommandError::VersionError(message)) => assert!(message.contains("--promote"), "{}", message),
+            result => panic!("expected a version error, got {:?}", result.map(|r| r.tag))
+        }
+    }
+}
+
+#[test]
+fn prepare_pre_release_should_not_add_a_changelog_section() {
+    let mut config = Config::fixture();
+    config.changelog = true;
+    let git = Git::new(Box::new(pre_release_mock()), DEFAULT_FORMAT.to_string());
+
+    let release = prepare(&config, &git, &"gateway".to_string(), &BumpArgs { pre: Some("rc".to_string()), ..args() }).unwrap();
+
+    assert!(release.files.is_empty());
+}
+
+#[test]
+fn prepare_promoted_version_should_add_changes_of_pre_releases_to_the_changelog() {
+    let mut config = Config::fixture();
+    config.changelog = true;
+    let git = Git::new(Box::new(pre_release_mock()), DEFAULT_FORMAT.to_string());
+
+    let release = prepare(&config, &git, &"gateway".to_string(), &BumpArgs { promote: true, ..args() }).unwrap();
+
+    assert_eq!(release.files.len(), 1);
+    assert_eq!(release.files[0].path, "gateway/CHANGELOG.md");
+    assert!(release.files[0].content.contains("### Added\n\n- add health route\n"));
+    assert!(release.files[0].content.contains("### Fixed\n\n- retry failed requests\n- handle timeout\n"));
+    assert!(!release.files[0].content.contains("rc."));
+}
+
+#[test]
+fn release_with_dry_run_should_not_create_anything() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("CHANGELOG.md").to_string_lossy().to_string();
+    let mut mock = MockGitCli::new();
+    mock.expect_commit_files().never();
+    mock.expect_create_tag().never();
+    mock.expect_push().never();
+
+    let git = Git::new(Box::new(mock), DEFAULT_FORMAT.to_string());
+    let files = vec![FileChange { path: path.clone(), content: "# Changelog".to_string() }];
+    release(&git, true, None, vec![release_plan("gateway", "1.1.0", files)], false, true, true).unwrap();
+
+    assert!(!dir.path().join("CHANGELOG.md").exists());
+}
+
+#[test]
+fn release_with_yes_should_skip_the_confirmation() {
+    let mut mock = MockGitCli::new();
+    mock.expect_get_commit_hash()
+        .with(eq("HEAD"))
+        .returning(|_| Ok("0ee0b0041380df22675472392ab54ff835b07b48".to_string()));
+    mock.expect_create_tag()
+        .with(eq("gateway/v1.1.0"), always())
+        .times(1)
+        .returning(|_, _| Ok(()));
+
+    let git = Git::new(Box::new(mock), DEFAULT_FORMAT.to_string());
+
+    release(&git, false, None, vec![release_plan("gateway", "1.1.0", vec![])], true, false, true).unwrap();
+}
+
+#[test]
+fn app_names_should_list_each_application_once() {
+    let args = BumpArgs { names: vec!["gateway".to_string(), "api".to_string(), "gateway".to_string()], ..args() };
+    let git = Git::new(Box::new(MockGitCli::new()), DEFAULT_FORMAT.to_string());
+
+    let names = app_names(&Config::default(), &git, &args).unwrap();
+
+    assert_eq!(names, vec!["gateway".to_string(), "api".to_string()]);
+}
+
+#[test]
+fn release_failing_for_second_app_should_delete_created_tags_and_reset_head() {
+    let dir = tempfile::tempdir().unwrap();
+    let gateway_changelog = dir.path().join("gateway.md").to_string_lossy().to_string();
+    let api_changelog = dir.path().join("api.md").to_string_lossy().to_string();
+    let mut mock = MockGitCli::new();
+    let mut head_calls = 0;
+    mock.expect_get_commit_hash()
+        .with(eq("HEAD"))
+        .returning(move |_| {
+            head_calls += 1;
+            Ok(if head_calls == 1 { "original".to_string() } else { "release".to_string() })
+        });
+    mock.expect_commit_files()
+        .with(eq(vec![gateway_changelog.clone()]), always())
+        .times(1)
+        .returning(|_, _| Ok(()));
+    mock.expect_commit_files()
+        .with(eq(vec![api_changelog.clone()]), always())
+        .times(1)
+        .returning(|_, _| Err(CommandError::ShellError("index.lock exists".to_string())));
+    mock.expect_create_tag()
+        .with(eq("gateway/v1.1.0"), always())
+        .times(1)
+        .returning(|_, _| Ok(()));
+    mock.expect_delete_tag()
+        .with(eq("gateway/v1.1.0"))
+        .times(1)
+        .returning(|_| Ok(()));
+    mock.expect_reset()
+        .with(eq("original"))
+        .times(1)
+        .returning(|_| Ok(()));
+    mock.expect_push().never();
+
+    let git = Git::new(Box::new(mock), DEFAULT_FORMAT.to_string());
+    let releases = vec![
+        release_plan("gateway", "1.1.0", vec![FileChange { path: gateway_changelog, content: "# Changelog".to_string() }]),
+        release_plan("api", "0.3.0", vec![FileChange { path: api_changelog, content: "# Changelog".to_string() }]),
+    ];
+
+    assert!(release(&git, true, None, releases, true, false, false).is_err());
+}
+
+#[test]
+fn release_failing_to_push_should_delete_created_tags() {
+    let mut mock = MockGitCli::new();
+    mock.expect_get_commit_hash()
+        .with(eq("HEAD"))
+        .returning(|_| Ok("original".to_string()));
+    mock.expect_get_config()
+        .with(eq("remote.origin.url"))
+        .returning(|_| Ok("git@github.com:kamilczerw/vemo.git".to_string()));
+    mock.expect_create_tag()
+        .times(2)
+        .returning(|_, _| Ok(()));
+    mock.expect_push()
+        .times(1)
+        .returning(|_, _| Err(CommandError::ShellError("rejected".to_string())));
+    mock.expect_delete_tag()
+        .with(eq("gateway/v1.1.0"))
+        .times(1)
+        .returning(|_| Ok(()));
+    mock.expect_delete_tag()
+        .with(eq("api/v0.3.0"))
+        .times(1)
+        .returning(|_| Ok(()));
+    mock.expect_reset().never();
+
+    let git = Git::new(Box::new(mock), DEFAULT_FORMAT.to_string());
+    let releases = vec![release_plan("gateway", "1.1.0", vec![]), release_plan("api", "0.3.0", vec![])];
+
+    assert!(release(&git, true, None, releases, true, false, false).is_err());
+}
+
+/// Git provider publishing only the first release
+struct FirstReleaseOnly {
+    published: Rc<RefCell<Vec<String>>>
+}
+
+impl GitClient for FirstReleaseOnly {
+    fn create_release(&self, _name: String, tag: Tag, _body: String, _target: String) -> Result<(), GitClientError> {
+        if !self.published.borrow().is_empty() {
+            return Err(GitClientError::ApiError { status: 422, message: "Validation Failed".to_string(), documentation_url: None })
+        }
+        self.published.borrow_mut().push(tag.formatted());
+        Ok(())
+    }
+}
+
+#[test]
+fn release_failing_in_git_provider_should_keep_pushed_tags() {
+    let mut mock = MockGitCli::new();
+    mock.expect_get_commit_hash()
+        .with(eq("HEAD"))
+        .returning(|_| Ok("original".to_string()));
+    mock.expect_create_tag()
+        .times(2)
+        .returning(|_, _| Ok(()));
+    mock.expect_delete_tag().never();
+    mock.expect_reset().never();
+
+    let git = Git::new(Box::new(mock), DEFAULT_FORMAT.to_string());
+    let published = Rc::new(RefCell::new(vec![]));
+    let client = FirstReleaseOnly { published: published.clone() };
+    let releases = vec![release_plan("gateway", "1.1.0", vec![]), release_plan("api", "0.3.0", vec![])];
+
+    let result = release(&git, false, Some(Box::new(client)), releases, true, false, false);
+
+    assert!(matches!(result, Err(CommandError::GitClientError(GitClientError::ApiError { status: 422, .. }))));
+    assert_eq!(*published.borrow(), vec!["gateway/v1.1.0".to_string()]);
+}
@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+use crate::commands::error::CommandError;
+use crate::commands::list::format_records;
+use crate::commands::shell::git::{Commit, Git, Tag};
+use crate::commands::{Component, OutputFormat};
+use crate::Config;
+
+use colored::Colorize;
+use log::debug;
+use serde_json::{json, Value};
+
+/// Application with commits in its path since the latest version
+pub struct ChangedApp {
+    pub name: String,
+    pub latest_tag: Option<Tag>,
+    pub commits: Vec<Commit>,
+    pub component: Option<Component>
+}
+
+pub fn run(config: Config, output: &OutputFormat) -> Result<(), CommandError> {
+    let git = Git::init(&config)?;
+    let apps = find_changed_apps(&config, &git)?;
+
+    if output != &OutputFormat::Text {
+        let records: Vec<Value> = apps.iter().map(record).collect();
+        println!("{}", format_records(&records, output)?.trim_end());
+        return Ok(())
+    }
+
+    if apps.is_empty() {
+        println!("{}", "No applications with unreleased changes found.".yellow());
+        return Ok(())
+    }
+
+    let length = apps.iter().map(|app| app.name.len()).max().unwrap_or(0);
+
+    println!("Applications with unreleased changes:");
+    for app in apps {
+        let version = app.latest_tag.map(|t| t.version.to_string()).unwrap_or_else(|| "-".to_string());
+        let component = app.component.map(|c| c.to_string()).unwrap_or_else(|| "none".to_string());
+        println!("   - {:width$} {} {} commits, suggested bump: {}",
+                 app.name.bold(), version.magenta(), app.commits.len(), component.bright_green(), width = length + 2)
+    }
+
+    Ok(())
+}
+
+/// Find configured applications which have commits in their path since the latest version
+pub fn find_changed_apps(config: &Config, git: &Git) -> Result<Vec<ChangedApp>, CommandError> {
+    let latest_tags: HashMap<String, Tag> = git.get_latest_tags()?.into_iter()
+        .map(|tag| (tag.app_name.clone(), tag))
+        .collect();
+
+    let mut names: Vec<&String> = config.apps.keys().collect();
+    names.sort();
+
+    let mut apps = vec![];
+    for name in names {
+        let path = match config.app_path(name) {
+            Some(path) => path,
+            None => {
+                debug!("Skipping {}, path is not configured", name);
+                continue
+            }
+        };
+
+        let latest_tag = latest_tags.get(name).cloned();
+        let commits = git.get_commits(latest_tag.clone(), &path)?;
+        if commits.is_empty() {
+            continue
+        }
+
+        let component = Component::from_commits(&commits);
+        apps.push(ChangedApp { name: name.clone(), latest_tag, commits, component });
+    }
+
+    Ok(apps)
+}
+
+fn record(app: &ChangedApp) -> Value {
+    json!({
+        "name": app.name,
+        "version": app.latest_tag.as_ref().map(|t| t.version.to_string()),
+        "tag": app.latest_tag.as_ref().map(|t| t.raw.clone()),
+        "commits": app.commits.len(),
+        "component": app.component.map(|c| c.to_string()),
+    })
+}
@@ -0,0 +1,38 @@
+use mockall::predicate::*;
+use crate::cfg::{AppConfig, Config, DEFAULT_FORMAT};
+use crate::commands::changed::find_changed_apps;
+use crate::commands::Component;
+use crate::commands::shell::git::{Commit, Git};
+use crate::commands::shell::MockGitCli;
+
+#[test]
+fn find_changed_apps_should_return_apps_with_commits_since_latest_tag() {
+    let mut mock = MockGitCli::new();
+    mock.expect_get_tags()
+        .returning(|_| Ok("gateway/v1.0.0\napi/v0.2.0".to_string()));
+    mock.expect_get_commits()
+        .with(eq(Some("gateway/v1.0.0".to_string())), eq("HEAD"), eq(Some("gateway".to_string())))
+        .returning(|_, _, _| Ok(vec![Commit::fixture("fix: timeout"), Commit::fixture("feat: add route")]));
+    mock.expect_get_commits()
+        .with(eq(Some("api/v0.2.0".to_string())), eq("HEAD"), eq(Some("api".to_string())))
+        .returning(|_, _, _| Ok(vec![]));
+    mock.expect_get_commits()
+        .with(eq(None), eq("HEAD"), eq(Some("new".to_string())))
+        .returning(|_, _, _| Ok(vec![Commit::fixture("chore: scaffold")]));
+
+    let mut config = Config::fixture();
+    config.apps.insert("api".to_string(), AppConfig { path: Some("api".to_string()), ..Default::default() });
+    config.apps.insert("new".to_string(), AppConfig { path: Some("new".to_string()), ..Default::default() });
+    config.apps.insert("no-path".to_string(), AppConfig::default());
+
+    let git = Git::new(Box::new(mock), DEFAULT_FORMAT.to_string());
+    let apps = find_changed_apps(&config, &git).unwrap();
+
+    assert_eq!(apps.len(), 2);
+    assert_eq!(apps[0].name, "gateway");
+    assert_eq!(apps[0].commits.len(), 2);
+    assert_eq!(apps[0].component, Some(Component::Minor));
+    assert_eq!(apps[1].name, "new");
+    assert_eq!(apps[1].latest_tag, None);
+    assert_eq!(apps[1].component, None);
+}
@@ -0,0 +1,247 @@
+use std::collections::HashSet;
+
+use crate::commands::bump;
+use crate::commands::bump::notes_base;
+use crate::commands::error::CommandError;
+use crate::commands::set::parse_version;
+use crate::commands::shell::git::{Commit, Git, Repo, Tag};
+use crate::commands::template;
+use crate::Config;
+
+use chrono::{Local, NaiveDate};
+use log::debug;
+use semver::Version;
+
+/// Revision the changelog ends at, unless `--to` is given
+const HEAD: &str = "HEAD";
+
+/// Beginning of a new changelog file, following https://keepachangelog.com
+const CHANGELOG_HEADER: &str = "# Changelog
+
+All notable changes to this project will be documented in this file.
+
+The format is based on [Keep a Changelog](https://keepachangelog.com/en/1.1.0/),
+and this project adheres to [Semantic Versioning](https://semver.org/spec/v2.0.0.html).
+
+## [Unreleased]
+";
+
+/// Keep a Changelog sections in the order they are rendered
+const SECTIONS: [&str; 3] = ["Added", "Changed", "Fixed"];
+
+pub fn run(config: Config, name: &str, from: &Option<String>, to: &Option<String>) -> Result<(), CommandError> {
+    let git = Git::init(&config)?;
+
+    let tags: Vec<Tag> = git.get_tags(Some(name.to_string()))?.into_iter()
+        .filter(|tag| tag.app_name == name)
+        .collect();
+
+    let (from, to, commits) = if from.is_none() && to.is_none() {
+        // Preview of the next version, with the changes bump would list in its release notes
+        let (from, commits) = preview_commits(&config, &git, name, &tags)?;
+        (from, HEAD.to_string(), commits)
+    } else {
+        let (from, to) = resolve_range(&tags, name, from, to)?;
+        let commits = app_commits(&config, &git, name, from.as_ref(), &to, config.app_path(name))?;
+        (from, to, commits)
+    };
+    debug!("Rendering changelog of {} from {:?} to {}", name, from.as_ref().map(|t| t.formatted()), to);
+
+    let to_tag = tags.iter().find(|tag| tag.raw == to);
+    let notes = ReleaseNotes { app_name: name, from: from.as_ref(), tag: to_tag, to: &to, commits: &commits };
+    println!("{}", release_notes(&config, &git, &notes)?.trim_end());
+
+    Ok(())
+}
+
+/// Version and commits the notes of the next version start from, the way `bump` computes them.
+///
+/// A pending pre-release is expected to be promoted, so the preview starts at the latest stable version.
+/// Without a configured "path" there are no commits, like in `bump`.
+pub(crate) fn preview_commits(config: &Config, git: &Git, name: &str, tags: &[Tag]) -> Result<(Option<Tag>, Vec<Commit>), CommandError> {
+    let latest_tag = tags.first().cloned();
+    let from = match latest_tag.clone() {
+        Some(latest) if !latest.version.pre.is_empty() => notes_base(git, name, &latest_tag, &latest.promote()?)?,
+        _ => latest_tag
+    };
+    let commits = bump::app_commits(config, git, name, from.as_ref())?.unwrap_or_default();
+
+    Ok((from, commits))
+}
+
+/// Commits of an application made after `from` up to a revision, in the application directory if it is given.
+///
+/// Release commits of its versions (changelog and version files, see `commit_message`) are left out,
+/// release notes of a version are rendered before that commit is made.
+pub(crate) fn app_commits(config: &Config, git: &Git, name: &str, from: Option<&Tag>, to: &str, dir: Option<String>) -> Result<Vec<Commit>, CommandError> {
+    let release_messages: HashSet<String> = git.get_tags(Some(name.to_string()))?.into_iter()
+        .filter(|tag| tag.app_name == name)
+        .map(|tag| config.commit_message(name, &tag.version, &tag.formatted()))
+        .collect();
+
+    let commits = git.get_commits_between(from, to, dir)?;
+
+    Ok(commits.into_iter()
+        .filter(|commit| {
+            let release_commit = release_messages.contains(&commit.message);
+            if release_commit {
+                debug!("Leaving out release commit {} of {}", commit.hash, name);
+            }
+            !release_commit
+        })
+        .collect())
+}
+
+/// Resolve the tag the changelog starts after and the revision it ends at.
+///
+/// `to` is either a version of the application or any git revision, HEAD by default. Without `from`,
+/// the changelog starts at the version preceding `to` (the preceding stable version when `to` is stable,
+/// like `bump` does), or at the latest version when `to` is not a version.
+pub(crate) fn resolve_range(tags: &[Tag], name: &str, from: &Option<String>, to: &Option<String>) -> Result<(Option<Tag>, String), CommandError> {
+    let to_tag = match to {
+        Some(to) => match parse_version(to) {
+            Ok(version) => Some(find_tag(tags, name, &version.to_string())?),
+            Err(_) => None
+        },
+        None => None
+    };
+    let to_ref = match (&to_tag, to) {
+        (Some(tag), _) => tag.raw.clone(),
+        (None, Some(to)) => to.clone(),
+        (None, None) => HEAD.to_string()
+    };
+
+    let from_tag = match (from, &to_tag) {
+        (Some(from), _) => Some(find_tag(tags, name, &parse_version(from)?.to_string())?),
+        // tags are ordered by version descending
+        (None, Some(to_tag)) => tags.iter()
+            .filter(|tag| !to_tag.version.pre.is_empty() || tag.version.pre.is_empty())
+            .find(|tag| tag.version < to_tag.version)
+            .cloned(),
+        (None, None) => tags.first().cloned()
+    };
+
+    Ok((from_tag, to_ref))
+}
+
+fn find_tag(tags: &[Tag], name: &str, version: &str) -> Result<Tag, CommandError> {
+    tags.iter()
+        .find(|tag| tag.version.to_string() == version)
+        .cloned()
+        .ok_or_else(|| CommandError::VersionError(format!("Version {} of {} not found", version, name)))
+}
+
+/// Commits of an application between two revisions, rendered as release notes
+pub(crate) struct ReleaseNotes<'a> {
+    pub app_name: &'a str,
+    /// Version the notes start after, `None` for the first version
+    pub from: Option<&'a Tag>,
+    /// Version the notes are for, `None` when they end at a revision which is not a version
+    pub tag: Option<&'a Tag>,
+    /// Revision the notes end at
+    pub to: &'a str,
+    pub commits: &'a [Commit]
+}
+
+/// Render markdown release notes with the release template of the application.
+///
+/// Without a template, the notes list the commits with a link comparing both revisions.
+pub(crate) fn release_notes(config: &Config, git: &Git, notes: &ReleaseNotes) -> Result<String, CommandError> {
+    let repo = repo_info(config, git);
+    let compare_url = repo.as_ref()
+        .zip(notes.from)
+        .and_then(|(repo, from)| repo.compare_url(&from.formatted(), notes.to));
+
+    if let Some(template) = config.release_template(notes.app_name) {
+        let date = Local::today().naive_local();
+        let context = template::context(notes.app_name, notes.from, notes.tag, date, notes.commits, repo.as_ref(), compare_url);
+        return template::render(&template, &context)
+    }
+
+    let mut body = String::from("## What's Changed\n\n");
+
+    for commit in notes.commits {
+        let short_hash: String = commit.hash.chars().take(7).collect();
+        let hash = match repo.as_ref().and_then(|repo| repo.commit_url(&commit.hash)) {
+            Some(url) => format!("[{}]({})", short_hash, url),
+            None => short_hash
+        };
+        body.push_str(&format!("* {} by {} in {}\n", commit.message, commit.author.email, hash));
+    }
+
+    if let Some(compare_url) = compare_url {
+        body.push_str(&format!("\n\n**Full Changelog**: {}", compare_url));
+    }
+
+    Ok(body)
+}
+
+/// Repo of the remote, with the provider configured for its host in the `[providers]` table
+fn repo_info(config: &Config, git: &Git) -> Option<Repo> {
+    let repo = git.get_repo_info().ok()?;
+    match config.provider_config(&repo.host) {
+        Some(provider_config) => Some(Repo { provider: provider_config.provider.clone(), ..repo }),
+        None => Some(repo)
+    }
+}
+
+/// Render a Keep a Changelog section of a version, `None` when none of the commits is listed.
+///
+/// `feat` commits are listed as Added, `fix` as Fixed, breaking changes and commits which are not
+/// Conventional Commits as Changed, as well as `perf`, `refactor` and `revert`. Other types
+/// (`chore`, `docs`, `ci`, ...) are left out.
+pub(crate) fn changelog_section(version: &Version, date: NaiveDate, commits: &[Commit]) -> Option<String> {
+    let mut section = format!("## [{}] - {}\n", version, date.format("%Y-%m-%d"));
+    let mut listed = false;
+
+    for name in SECTIONS {
+        let entries: Vec<String> = commits.iter()
+            .filter(|commit| changelog_section_name(commit) == Some(name))
+            .map(|commit| if commit.is_breaking() {
+                format!("- **BREAKING:** {}", commit.description())
+            } else {
+                format!("- {}", commit.description())
+            })
+            .collect();
+
+        if !entries.is_empty() {
+            section.push_str(&format!("\n### {}\n\n{}\n", name, entries.join("\n")));
+            listed = true;
+        }
+    }
+
+    Some(section).filter(|_| listed)
+}
+
+fn changelog_section_name(commit: &Commit) -> Option<&'static str> {
+    if commit.is_breaking() {
+        return Some("Changed")
+    }
+
+    match commit.commit_type().as_deref() {
+        Some("feat") => Some("Added"),
+        Some("fix") => Some("Fixed"),
+        Some("perf") | Some("refactor") | Some("revert") | None => Some("Changed"),
+        Some(_) => None
+    }
+}
+
+/// Insert a version section into a changelog, below the Unreleased section and above older versions.
+///
+/// A new changelog gets the Keep a Changelog header, the rest of an existing file is left untouched.
+pub(crate) fn insert_section(changelog: Option<&str>, section: &str) -> String {
+    let changelog = match changelog.filter(|c| !c.trim().is_empty()) {
+        Some(changelog) => changelog,
+        None => return format!("{}\n{}", CHANGELOG_HEADER, section)
+    };
+
+    let position = changelog.match_indices("## ")
+        .map(|(index, _)| index)
+        .filter(|index| *index == 0 || changelog[..*index].ends_with('\n'))
+        .find(|index| !changelog[*index..].to_lowercase().starts_with("## [unreleased]"));
+
+    match position {
+        Some(index) => format!("{}{}\n{}", &changelog[..index], section, &changelog[index..]),
+        None => format!("{}\n\n{}", changelog.trim_end(), section)
+    }
+}
@@ -0,0 +1,192 @@
+use chrono::NaiveDate;
+use mockall::predicate::*;
+use semver::Version;
+use crate::cfg::{AppConfig, Config, DEFAULT_FORMAT};
+use crate::commands::changelog::{app_commits, changelog_section, insert_section, preview_commits, resolve_range};
+use crate::commands::shell::git::{Commit, Git, Tag};
+use crate::commands::shell::MockGitCli;
+
+/// Tags ordered by version descending, the way `Git::get_tags` returns them
+fn tags() -> Vec<Tag> {
+    ["1.1.0", "1.0.0", "0.1.0"].iter()
+        .map(|v| Tag::fixture("gateway", v))
+        .collect()
+}
+
+#[test]
+fn resolve_range_without_arguments_should_start_at_latest_version_and_end_at_head() {
+    let (from, to) = resolve_range(&tags(), "gateway", &None, &None).unwrap();
+
+    assert_eq!(from.unwrap().raw, "gateway/v1.1.0");
+    assert_eq!(to, "HEAD");
+}
+
+#[test]
+fn resolve_range_with_to_version_should_start_at_the_preceding_version() {
+    let (from, to) = resolve_range(&tags(), "gateway", &None, &Some("1.0.0".to_string())).unwrap();
+
+    assert_eq!(from.unwrap().raw, "gateway/v0.1.0");
+    assert_eq!(to, "gateway/v1.0.0");
+}
+
+#[test]
+fn resolve_range_with_to_stable_version_should_skip_pre_releases() {
+    let tags: Vec<Tag> = ["1.1.0", "1.1.0-rc.2", "1.1.0-rc.1", "1.0.0"].iter()
+        .map(|v| Tag::fixture("gateway", v))
+        .collect();
+
+    let (from, _) = resolve_range(&tags, "gateway", &None, &Some("1.1.0".to_string())).unwrap();
+    assert_eq!(from.unwrap().raw, "gateway/v1.0.0");
+
+    let (from, _) = resolve_range(&tags, "gateway", &None, &Some("1.1.0-rc.2".to_string())).unwrap();
+    assert_eq!(from.unwrap().raw, "gateway/v1.1.0-rc.1");
+}
+
+#[test]
+fn resolve_range_with_first_version_should_start_at_the_beginning() {
+    let (from, to) = resolve_range(&tags(), "gateway", &None, &Some("v0.1.0".to_string())).unwrap();
+
+    assert!(from.is_none());
+    assert_eq!(to, "gateway/v0.1.0");
+}
+
+#[test]
+fn resolve_range_with_from_version_and_git_ref_should_use_both() {
+    let (from, to) = resolve_range(&tags(), "gateway", &Some("0.1.0".to_string()), &Some("main".to_string())).unwrap();
+
+    assert_eq!(from.unwrap().raw, "gateway/v0.1.0");
+    assert_eq!(to, "main");
+}
+
+#[test]
+fn resolve_range_with_unknown_version_should_fail() {
+    assert!(resolve_range(&tags(), "gateway", &Some("0.5.0".to_string()), &None).is_err());
+    assert!(resolve_range(&tags(), "gateway", &None, &Some("2.0.0".to_string())).is_err());
+}
+
+#[test]
+fn changelog_section_should_group_commits_by_change_type() {
+    let commits = vec![
+        Commit::fixture("fix(api): handle timeout"),
+        Commit::fixture("feat: add route"),
+        Commit::fixture("chore: update deps"),
+        Commit::fixture("refactor!: rename config keys"),
+        Commit::fixture("Update readme"),
+    ];
+    let date = NaiveDate::from_ymd(2022, 6, 25);
+
+    let section = changelog_section(&Version::new(1, 2, 0), date, &commits);
+
+    assert_eq!(section.as_deref(), Some("## [1.2.0] - 2022-06-25\n\
+        \n### Added\n\n- add route\n\
+        \n### Changed\n\n- **BREAKING:** rename config keys\n- Update readme\n\
+        \n### Fixed\n\n- api: handle timeout\n"));
+}
+
+#[test]
+fn changelog_section_without_listed_changes_should_be_none() {
+    let commits = vec![Commit::fixture("chore: update deps"), Commit::fixture("docs: fix typo")];
+    let date = NaiveDate::from_ymd(2022, 6, 25);
+
+    assert_eq!(changelog_section(&Version::new(1, 2, 1), date, &commits), None);
+    assert_eq!(changelog_section(&Version::new(1, 2, 1), date, &[]), None);
+}
+
+#[test]
+fn insert_section_into_a_missing_changelog_should_add_the_header() {
+    let changelog = insert_section(None, "## [0.1.0] - 2022-06-25\n");
+
+    assert!(changelog.starts_with("# Changelog\n"));
+    assert!(changelog.ends_with("## [Unreleased]\n\n## [0.1.0] - 2022-06-25\n"));
+}
+
+#[test]
+fn insert_section_should_put_the_version_below_unreleased_and_above_older_versions() {
+    let existing = "# Changelog\n\n## [Unreleased]\n\n- work in progress\n\n## [1.0.0] - 2022-06-01\n\n### Added\n\n- first\n";
+
+    let changelog = insert_section(Some(existing), "## [1.1.0] - 2022-06-25\n\n### Fixed\n\n- bug\n");
+
+    assert_eq!(changelog, "# Changelog\n\n## [Unreleased]\n\n- work in progress\n\n\
+        ## [1.1.0] - 2022-06-25\n\n### Fixed\n\n- bug\n\n\
+        ## [1.0.0] - 2022-06-01\n\n### Added\n\n- first\n");
+}
+
+#[test]
+fn insert_section_without_older_versions_should_append_the_version() {
+    let changelog = insert_section(Some("# Changelog\n\n## [Unreleased]\n"), "## [1.0.0] - 2022-06-25\n");
+
+    assert_eq!(changelog, "# Changelog\n\n## [Unreleased]\n\n## [1.0.0] - 2022-06-25\n");
+}
+
+#[test]
+fn app_commits_should_leave_out_release_commits_of_all_versions() {
+    let config = Config::fixture();
+    let mut mock = MockGitCli::new();
+    mock.expect_get_tags()
+        .returning(|_| Ok("gateway/v0.1.0\ngateway/v0.1.1-rc.1\ngateway/v0.1.1-rc.2".to_string()));
+    mock.expect_get_commits()
+        .with(eq(Some("gateway/v0.1.0".to_string())), eq("gateway/v0.1.1-rc.2"), eq(None))
+        .returning(|_, _, _| Ok(vec![
+            Commit::fixture("chore(release): gateway v0.1.1-rc.2"),
+            Commit::fixture("feat: add health route"),
+            Commit::fixture("chore(release): gateway v0.1.1-rc.1"),
+            Commit::fixture("fix: handle timeout"),
+        ]));
+    let git = Git::new(Box::new(mock), DEFAULT_FORMAT.to_string());
+    let from = Tag::fixture("gateway", "0.1.0");
+
+    let commits = app_commits(&config, &git, "gateway", Some(&from), "gateway/v0.1.1-rc.2", None).unwrap();
+
+    assert_eq!(commits, vec![Commit::fixture("feat: add health route"), Commit::fixture("fix: handle timeout")]);
+}
+
+#[test]
+fn app_commits_should_keep_commits_of_versions_tagged_without_files() {
+    let config = Config::fixture();
+    let mut mock = MockGitCli::new();
+    mock.expect_get_tags().returning(|_| Ok("gateway/v0.1.0".to_string()));
+    mock.expect_get_commits()
+        .returning(|_, _, _| Ok(vec![Commit::fixture("fix: handle timeout"), Commit::fixture("feat: add route")]));
+    let git = Git::new(Box::new(mock), DEFAULT_FORMAT.to_string());
+
+    let commits = app_commits(&config, &git, "gateway", None, "HEAD", None).unwrap();
+
+    assert_eq!(commits, vec![Commit::fixture("fix: handle timeout"), Commit::fixture("feat: add route")]);
+}
+
+#[test]
+fn preview_commits_after_pre_release_should_start_at_the_latest_stable_version() {
+    let config = Config::fixture();
+    let mut mock = MockGitCli::new();
+    mock.expect_get_tags()
+        .returning(|_| Ok("gateway/v0.1.0\ngateway/v0.1.1-rc.1".to_string()));
+    mock.expect_get_commits()
+        .with(eq(Some("gateway/v0.1.0".to_string())), eq("HEAD"), eq(Some("gateway".to_string())))
+        .times(1)
+        .returning(|_, _, _| Ok(vec![
+            Commit::fixture("feat: add health route"),
+            Commit::fixture("chore(release): gateway v0.1.1-rc.1"),
+            Commit::fixture("fix: handle timeout"),
+        ]));
+    let git = Git::new(Box::new(mock), DEFAULT_FORMAT.to_string());
+    let tags = git.get_tags(Some("gateway".to_string())).unwrap();
+
+    let (from, commits) = preview_commits(&config, &git, "gateway", &tags).unwrap();
+
+    assert_eq!(from.unwrap().raw, "gateway/v0.1.0");
+    assert_eq!(commits, vec![Commit::fixture("feat: add health route"), Commit::fixture("fix: handle timeout")]);
+}
+
+#[test]
+fn preview_commits_without_path_should_not_list_commits() {
+    let mut config = Config::fixture();
+    config.apps.insert("gateway".to_string(), AppConfig::default());
+    let mut mock = MockGitCli::new();
+    mock.expect_get_commits().never();
+    let git = Git::new(Box::new(mock), DEFAULT_FORMAT.to_string());
+
+    let (from, commits) = preview_commits(&config, &git, "gateway", &tags()).unwrap();
+
+    assert_eq!(from.unwrap().raw, "gateway/v1.1.0");
+    assert!(commits.is_empty());
+}
@@ -3,6 +3,7 @@ use std::string::FromUtf8Error;
 use crate::git::client::error::GitClientError;
 
 #[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
 pub enum CommandError {
     /// Returned when parsing of shell command output cannot be converted to string
     ParseError(String),
@@ -11,7 +12,22 @@ pub enum CommandError {
     ShellError(String),
 
     /// Returned when git client failed
-    GitClientError(GitClientError)
+    GitClientError(GitClientError),
+
+    /// Returned when a new version cannot be computed from the current one
+    VersionError(String),
+
+    /// Returned when a file in the repo cannot be read or written
+    FileError(String),
+
+    /// Returned when a release template cannot be rendered
+    TemplateError(String),
+
+    /// Returned when arguments are missing and cannot be inferred
+    UsageError(String),
+
+    /// Returned when the confirmation cannot be read or the release body cannot be edited
+    PromptError(String)
 }
 
 impl From<Utf8Error> for CommandError {
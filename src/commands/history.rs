@@ -0,0 +1,77 @@
+use crate::commands::error::CommandError;
+use crate::commands::list::format_records;
+use crate::commands::shell::git::{Git, Tag, TagInfo};
+use crate::commands::OutputFormat;
+use crate::Config;
+
+use colored::Colorize;
+use serde_json::{json, Value};
+
+/// Released version of an application
+pub struct HistoryEntry {
+    pub tag: Tag,
+    pub info: Option<TagInfo>,
+    /// Number of commits since the previous version, in the application path if it is configured
+    pub commits: usize
+}
+
+pub fn run(config: Config, name: &str, limit: &Option<usize>, output: &OutputFormat) -> Result<(), CommandError> {
+    let git = Git::init(&config)?;
+    let entries = find_history(&config, &git, name, limit)?;
+
+    if output != &OutputFormat::Text {
+        let records: Vec<Value> = entries.iter().map(record).collect();
+        println!("{}", format_records(&records, output)?.trim_end());
+        return Ok(())
+    }
+
+    if entries.is_empty() {
+        println!("{}", format!("No versions of {} found.", name).yellow());
+        return Ok(())
+    }
+
+    let length = entries.iter().map(|e| e.tag.version.to_string().len()).max().unwrap_or(0);
+
+    println!("History of {}:", name.bold());
+    for entry in entries {
+        let date = entry.info.as_ref().map(|i| i.date.format("%Y-%m-%d %H:%M").to_string()).unwrap_or_else(|| "-".to_string());
+        let commit = entry.info.as_ref().map(|i| i.commit.chars().take(7).collect()).unwrap_or_else(|| "-".to_string());
+        let tagger = entry.info.as_ref().and_then(|i| i.tagger.clone()).unwrap_or_else(|| "-".to_string());
+        println!("   - {:width$} {} {} {} commits by {}",
+                 entry.tag.version.to_string().magenta(), date, commit.bright_black(), entry.commits, tagger,
+                 width = length + 2)
+    }
+
+    Ok(())
+}
+
+/// Versions of an application, newest first, with the number of commits since the previous version
+pub fn find_history(config: &Config, git: &Git, name: &str, limit: &Option<usize>) -> Result<Vec<HistoryEntry>, CommandError> {
+    let tags: Vec<Tag> = git.get_tags(Some(name.to_string()))?.into_iter()
+        .filter(|tag| tag.app_name == name)
+        .collect();
+    let mut infos = git.get_tag_infos(Some(name.to_string()))?;
+    let path = config.app_path(name);
+
+    let count = limit.unwrap_or(tags.len()).min(tags.len());
+
+    let mut entries = vec![];
+    for (index, tag) in tags.iter().take(count).enumerate() {
+        let previous = tags.get(index + 1);
+        let commits = git.count_commits(previous, tag, path.clone())?;
+        entries.push(HistoryEntry { tag: tag.clone(), info: infos.remove(&tag.raw), commits });
+    }
+
+    Ok(entries)
+}
+
+fn record(entry: &HistoryEntry) -> Value {
+    json!({
+        "version": entry.tag.version.to_string(),
+        "tag": entry.tag.raw,
+        "date": entry.info.as_ref().map(|i| i.date.to_rfc3339()),
+        "tagger": entry.info.as_ref().and_then(|i| i.tagger.clone()),
+        "commit": entry.info.as_ref().map(|i| i.commit.clone()),
+        "commits": entry.commits,
+    })
+}
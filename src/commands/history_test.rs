@@ -0,0 +1,57 @@
+use mockall::predicate::*;
+use crate::cfg::{Config, DEFAULT_FORMAT};
+use crate::commands::history::find_history;
+use crate::commands::shell::git::Git;
+use crate::commands::shell::MockGitCli;
+
+fn mock() -> MockGitCli {
+    let mut mock = MockGitCli::new();
+    mock.expect_get_tags()
+        .with(eq("gateway/v*".to_string()))
+        .returning(|_| Ok("gateway/v1.0.0\ngateway/v1.1.0\ngateway/v0.1.0".to_string()));
+    mock.expect_get_tag_refs()
+        .with(eq("gateway/v*".to_string()))
+        .returning(|_| Ok("gateway/v1.1.0\x1f0ee0b0041380df22675472392ab54ff835b07b48\x1f2022-06-25T20:57:13+02:00\x1fKamil Czerwiński\n\
+            gateway/v1.0.0\x1f1ee0b0041380df22675472392ab54ff835b07b48\x1f2022-06-20T10:00:00+02:00\x1f\n\
+            gateway/v0.1.0\x1f2ee0b0041380df22675472392ab54ff835b07b48\x1f2022-06-01T10:00:00+02:00\x1fKamil Czerwiński\n".to_string()));
+    mock
+}
+
+#[test]
+fn find_history_should_return_versions_newest_first_with_commit_counts() {
+    let mut mock = mock();
+    mock.expect_count_commits()
+        .with(eq(Some("gateway/v1.0.0".to_string())), eq("gateway/v1.1.0"), eq(Some("gateway".to_string())))
+        .returning(|_, _, _| Ok(3));
+    mock.expect_count_commits()
+        .with(eq(Some("gateway/v0.1.0".to_string())), eq("gateway/v1.0.0"), eq(Some("gateway".to_string())))
+        .returning(|_, _, _| Ok(5));
+    mock.expect_count_commits()
+        .with(eq(None), eq("gateway/v0.1.0"), eq(Some("gateway".to_string())))
+        .returning(|_, _, _| Ok(1));
+
+    let git = Git::new(Box::new(mock), DEFAULT_FORMAT.to_string());
+    let entries = find_history(&Config::fixture(), &git, "gateway", &None).unwrap();
+
+    let versions: Vec<String> = entries.iter().map(|e| e.tag.version.to_string()).collect();
+    assert_eq!(versions, vec!["1.1.0", "1.0.0", "0.1.0"]);
+    assert_eq!(entries.iter().map(|e| e.commits).collect::<Vec<usize>>(), vec![3, 5, 1]);
+    assert_eq!(entries[0].info.as_ref().unwrap().tagger, Some("Kamil Czerwiński".to_string()));
+    assert_eq!(entries[1].info.as_ref().unwrap().tagger, None);
+}
+
+#[test]
+fn find_history_with_limit_should_return_only_latest_versions() {
+    let mut mock = mock();
+    mock.expect_count_commits()
+        .with(eq(Some("gateway/v1.0.0".to_string())), eq("gateway/v1.1.0"), eq(Some("gateway".to_string())))
+        .times(1)
+        .returning(|_, _, _| Ok(3));
+
+    let git = Git::new(Box::new(mock), DEFAULT_FORMAT.to_string());
+    let entries = find_history(&Config::fixture(), &git, "gateway", &Some(1)).unwrap();
+
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].tag.raw, "gateway/v1.1.0");
+    assert_eq!(entries[0].commits, 3);
+}
@@ -1,13 +1,25 @@
 use crate::commands::error::CommandError;
-use crate::commands::shell::git::Git;
+use crate::commands::shell::git::{Git, Tag, TagInfo};
+use crate::commands::OutputFormat;
 use crate::Config;
 
 use colored::Colorize;
+use serde_json::{json, Value};
 
-pub fn run(config: Config) -> Result<(), CommandError> {
-    let git = Git::init(config.format);
+pub fn run(config: Config, output: &OutputFormat) -> Result<(), CommandError> {
+    let git = Git::init(&config)?;
 
     let apps = git.get_latest_tags()?;
+
+    if output != &OutputFormat::Text {
+        let infos = git.get_tag_infos(None)?;
+        let records: Vec<Value> = apps.iter()
+            .map(|tag| record(tag, infos.get(&tag.raw)))
+            .collect();
+        println!("{}", format_records(&records, output)?.trim_end());
+        return Ok(())
+    }
+
     if apps.is_empty() {
         println!("{}", "💩 No applications found in the repo. Try creating new one by running: "
             .yellow());
@@ -27,3 +39,55 @@ pub fn run(config: Config) -> Result<(), CommandError> {
 
     Ok(())
 }
+
+fn record(tag: &Tag, info: Option<&TagInfo>) -> Value {
+    json!({
+        "name": tag.app_name,
+        "version": tag.version.to_string(),
+        "tag": tag.raw,
+        "commit": info.map(|i| i.commit.clone()),
+        "date": info.map(|i| i.date.to_rfc3339()),
+    })
+}
+
+/// Render records in a machine-readable format, csv and tsv get a header with the record keys
+pub(crate) fn format_records(records: &[Value], output: &OutputFormat) -> Result<String, CommandError> {
+    match output {
+        OutputFormat::Json | OutputFormat::Text => serde_json::to_string_pretty(records)
+            .map_err(|e| CommandError::ParseError(format!("Failed to serialize output, {}", e))),
+        OutputFormat::Yaml => serde_yaml::to_string(records)
+            .map_err(|e| CommandError::ParseError(format!("Failed to serialize output, {}", e))),
+        OutputFormat::Csv => Ok(delimited(records, ',')),
+        OutputFormat::Tsv => Ok(delimited(records, '\t'))
+    }
+}
+
+fn delimited(records: &[Value], separator: char) -> String {
+    let keys: Vec<String> = match records.first() {
+        Some(Value::Object(first)) => first.keys().cloned().collect(),
+        _ => return String::new()
+    };
+
+    let mut lines = vec![join(keys.iter().map(String::as_str), separator)];
+    for record in records {
+        let values: Vec<String> = keys.iter().map(|key| match &record[key] {
+            Value::Null => String::new(),
+            Value::String(s) => s.clone(),
+            other => other.to_string()
+        }).collect();
+        lines.push(join(values.iter().map(String::as_str), separator));
+    }
+
+    lines.join("\n")
+}
+
+fn join<'a>(fields: impl Iterator<Item = &'a str>, separator: char) -> String {
+    fields.map(|field| {
+        let needs_quotes = field.contains(separator) || field.contains('"') || field.contains('\n');
+        if needs_quotes {
+            format!("\"{}\"", field.replace('"', "\"\""))
+        } else {
+            field.to_string()
+        }
+    }).collect::<Vec<String>>().join(&separator.to_string())
+}
@@ -0,0 +1,44 @@
+use serde_json::json;
+use crate::commands::list::format_records;
+use crate::commands::OutputFormat;
+
+#[test]
+fn format_records_as_csv_should_add_header_and_quote_values() {
+    let records = vec![
+        json!({ "name": "gateway", "version": "1.0.0", "commit": null }),
+        json!({ "name": "app, \"legacy\"", "version": "0.1.0", "commit": "0ee0b00" }),
+    ];
+
+    let output = format_records(&records, &OutputFormat::Csv).unwrap();
+
+    assert_eq!(output, "name,version,commit\n\
+        gateway,1.0.0,\n\
+        \"app, \"\"legacy\"\"\",0.1.0,0ee0b00");
+}
+
+#[test]
+fn format_records_as_tsv_should_separate_values_with_tabs() {
+    let records = vec![json!({ "name": "gateway", "version": "1.0.0" })];
+
+    let output = format_records(&records, &OutputFormat::Tsv).unwrap();
+
+    assert_eq!(output, "name\tversion\ngateway\t1.0.0");
+}
+
+#[test]
+fn format_records_as_json_should_return_an_array() {
+    let records = vec![json!({ "name": "gateway", "version": "1.0.0" })];
+
+    let output = format_records(&records, &OutputFormat::Json).unwrap();
+
+    assert_eq!(serde_json::from_str::<serde_json::Value>(&output).unwrap(), json!(records));
+}
+
+#[test]
+fn format_records_as_yaml_should_return_a_list() {
+    let records = vec![json!({ "name": "gateway", "version": "1.0.0" })];
+
+    let output = format_records(&records, &OutputFormat::Yaml).unwrap();
+
+    assert!(output.contains("- name: gateway\n  version: 1.0.0"));
+}
@@ -1,17 +1,81 @@
 pub mod bump;
+pub mod changed;
+pub mod changelog;
+pub mod history;
 pub mod list;
+pub mod set;
+pub mod template;
+pub mod version_file;
 pub mod error;
 pub mod shell;
 
+#[cfg(test)]
+mod bump_test;
+
+#[cfg(test)]
+mod list_test;
+
+#[cfg(test)]
+mod changed_test;
+
+#[cfg(test)]
+mod set_test;
+
+#[cfg(test)]
+mod history_test;
+
+#[cfg(test)]
+mod changelog_test;
+
+#[cfg(test)]
+mod version_file_test;
+
+#[cfg(test)]
+mod template_test;
+
+use std::fmt::{Display, Formatter};
 use clap::{ArgEnum, Subcommand};
+use crate::commands::bump::BumpArgs;
+use crate::commands::set::SetArgs;
+use crate::commands::shell::git::Commit;
 
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ArgEnum)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ArgEnum)]
 pub enum Component {
     Major,
     Minor,
     Patch
 }
 
+impl Component {
+    /// The most significant component implied by the commits, if any of them is releasable
+    pub fn from_commits(commits: &[Commit]) -> Option<Component> {
+        commits.iter()
+            .filter_map(|commit| commit.bump_component())
+            .min()
+    }
+}
+
+impl Display for Component {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Component::Major => write!(f, "major"),
+            Component::Minor => write!(f, "minor"),
+            Component::Patch => write!(f, "patch")
+        }
+    }
+}
+
+/// Format of the command output
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ArgEnum)]
+pub enum OutputFormat {
+    /// Human readable text
+    Text,
+    Json,
+    Yaml,
+    Csv,
+    Tsv
+}
+
 #[derive(Subcommand)]
 pub enum Commands {
     /// List all available applications.
@@ -19,13 +83,52 @@ pub enum Commands {
     /// The format variable is used to filter out the tags in the monorepo.
     ///
     /// Default format is "{app_name}/v{version}", it can be changed using .vemo.toml or VEMO_FORMAT env variable.
-    List {},
-    Bump {
+    List {
+        /// Output format, machine-readable formats include the tag, its commit and date
+        #[clap(short, long, arg_enum, default_value = "text")]
+        output: OutputFormat
+    },
+    /// List applications with unreleased changes.
+    ///
+    /// An application has changed when there are commits in its configured "path" since its latest version.
+    /// Applications without "path" in .vemo.toml are skipped.
+    Changed {
+        /// Output format
+        #[clap(short, long, arg_enum, default_value = "text")]
+        output: OutputFormat
+    },
+    /// Show released versions of an application.
+    ///
+    /// Each version is listed with the date and author of its tag, the commit it points to and the number
+    /// of commits since the previous version (only in the application "path" if it is configured).
+    History {
         /// Name of application inside a monorepo
         name: String,
 
-        /// Version component which will be bumped
-        #[clap(short, long, arg_enum, default_value = "minor")]
-        component: Component
-    }
+        /// Show only the given number of latest versions
+        #[clap(short, long)]
+        limit: Option<usize>,
+
+        /// Output format
+        #[clap(short, long, arg_enum, default_value = "text")]
+        output: OutputFormat
+    },
+    /// Render release notes of an application between two versions.
+    ///
+    /// Without --from, the notes start at the version preceding --to. Without both, they preview the
+    /// notes `bump` would create for the next stable version, up to HEAD.
+    Changelog {
+        /// Name of application inside a monorepo
+        name: String,
+
+        /// Version the notes start after
+        #[clap(long, value_name = "VERSION")]
+        from: Option<String>,
+
+        /// Version or git revision the notes end at
+        #[clap(long, value_name = "VERSION|REF")]
+        to: Option<String>
+    },
+    Bump(BumpArgs),
+    Set(SetArgs)
 }
@@ -0,0 +1,79 @@
+use std::io::{IsTerminal, stdin};
+use clap::Args;
+use semver::Version;
+use log::debug;
+use crate::cfg::Config;
+use crate::commands::bump::{notes_base, release, Release};
+use crate::commands::error::CommandError;
+use crate::commands::shell::git::{Git, Tag};
+use crate::git::GitClient;
+
+/// Release an explicit version of an application.
+///
+/// The version has to be greater than the latest version of the application, unless --force is used.
+#[derive(Args)]
+pub struct SetArgs {
+    /// Name of application inside a monorepo
+    pub name: String,
+
+    /// Version to release (e.g. 2.0.0 or 1.4.0-rc.1)
+    pub version: String,
+
+    /// Release the version even if it is not greater than the latest version
+    #[clap(long)]
+    pub force: bool,
+
+    /// Create the release without asking for confirmation
+    #[clap(short, long)]
+    pub yes: bool,
+
+    /// Print the computed tag, release name and body without creating anything
+    #[clap(long)]
+    pub dry_run: bool
+}
+
+pub fn run(config: Config, args: &SetArgs, git_client: Option<Box<dyn GitClient>>) -> Result<(), CommandError> {
+    let git = Git::init(&config)?;
+
+    let version = parse_version(&args.version)?;
+    let latest_tag = git.find_latest_tag(&args.name)?;
+    check_version(&latest_tag, &version, args.force)?;
+
+    let tag = Tag::new_with_format(&config.format, &args.name, version);
+    if git.get_tags(Some(args.name.clone()))?.iter().any(|t| t.raw == tag.raw) {
+        return Err(CommandError::VersionError(format!("Tag {} already exists", tag)))
+    }
+
+    let from = notes_base(&git, &args.name, &latest_tag, &tag)?;
+    let commits = config.app_path(&args.name)
+        .map(|path| git.get_commits(from.clone(), &path))
+        .transpose()?
+        .unwrap_or_default();
+
+    let release_plan = Release::new(&config, &git, &args.name, &from, tag, commits)?;
+
+    release(&git, config.push, git_client, vec![release_plan], args.yes, args.dry_run, stdin().is_terminal())
+}
+
+/// Parse a semantic version, the `v` prefix is accepted (`v1.2.0`)
+pub(crate) fn parse_version(version: &str) -> Result<Version, CommandError> {
+    let trimmed = version.strip_prefix('v').unwrap_or(version);
+
+    Version::parse(trimmed)
+        .map_err(|e| CommandError::VersionError(format!("Invalid version \"{}\", {}", version, e)))
+}
+
+/// Make sure the version is greater than the latest version, unless it is forced
+pub(crate) fn check_version(latest_tag: &Option<Tag>, version: &Version, force: bool) -> Result<(), CommandError> {
+    match latest_tag {
+        Some(latest) if version <= &latest.version && force => {
+            debug!("Version {} is not greater than {}, releasing it anyway", version, latest);
+            Ok(())
+        }
+        Some(latest) if version <= &latest.version => Err(CommandError::VersionError(format!(
+            "Version {} is not greater than the latest version {}, use --force to release it anyway",
+            version, latest
+        ))),
+        _ => Ok(())
+    }
+}
@@ -0,0 +1,27 @@
+use semver::Version;
+use crate::commands::set::{check_version, parse_version};
+use crate::commands::shell::git::Tag;
+
+#[test]
+fn parse_version_should_accept_v_prefix() {
+    assert_eq!(parse_version("v1.2.0").unwrap(), Version::new(1, 2, 0));
+    assert_eq!(parse_version("2.0.0-rc.1").unwrap(), Version::parse("2.0.0-rc.1").unwrap());
+}
+
+#[test]
+fn parse_version_should_fail_for_invalid_version() {
+    assert!(parse_version("1.2").is_err());
+}
+
+#[test]
+fn check_version_should_allow_greater_version() {
+    assert!(check_version(&Some(Tag::fixture("gateway", "1.2.0")), &Version::new(2, 0, 0), false).is_ok());
+    assert!(check_version(&None, &Version::new(0, 0, 1), false).is_ok());
+}
+
+#[test]
+fn check_version_should_fail_for_lower_or_equal_version_unless_forced() {
+    assert!(check_version(&Some(Tag::fixture("gateway", "1.2.0")), &Version::new(1, 2, 0), false).is_err());
+    assert!(check_version(&Some(Tag::fixture("gateway", "1.2.0")), &Version::parse("1.2.0-rc.1").unwrap(), false).is_err());
+    assert!(check_version(&Some(Tag::fixture("gateway", "1.2.0")), &Version::new(1, 1, 0), true).is_ok());
+}
@@ -1,12 +1,26 @@
 use chrono::ParseError;
+use lazy_static::lazy_static;
+use regex::Regex;
+use crate::commands::Component;
 use crate::commands::error::CommandError;
 
+lazy_static! {
+    /// Header of a Conventional Commit message, e.g. `feat(api)!: add route`
+    static ref CONVENTIONAL_COMMIT: Regex = Regex::new(r"^(?P<type>[a-zA-Z]+)(\((?P<scope>[^)]*)\))?(?P<breaking>!)?:\s+(?P<description>.*)$").unwrap();
+    /// `BREAKING CHANGE:` footer line, matched against the body only
+    static ref BREAKING_CHANGE_FOOTER: Regex = Regex::new(r"(?m)^BREAKING[ -]CHANGE:").unwrap();
+}
+
 #[derive(Debug, Clone, Eq, Ord, PartialEq, PartialOrd)]
 pub struct Commit {
     pub hash: String,
     pub message: String,
     pub author: Author,
-    pub date: chrono::DateTime<chrono::Utc>
+    pub date: chrono::DateTime<chrono::Utc>,
+    /// Message without the subject, trailers included
+    pub body: String,
+    /// Trailers of the message (`Signed-off-by: ...`, `Refs: ...`) as key and value, in order
+    pub trailers: Vec<(String, String)>
 }
 
 #[derive(Debug, Clone, Eq, Ord, PartialEq, PartialOrd)]
@@ -16,18 +30,25 @@ pub struct Author {
 }
 
 impl Commit {
-    /// Create a new commit from a git log line
-    /// format:%H;%aN;%aE;%s;%cI
-    pub(crate) fn from_line(line_output: &str) -> Result<Self, CommandError> {
-        let mut line = line_output.to_string();
-        line.retain(|c| c != '\n');
-        let mut parts = line.split(';');
-        let hash = parts.next().ok_or(CommandError::ShellError("Failed to parse commit hash".to_string()))?.to_string();
+    /// Create a new commit from a git log record, fields are separated with the unit separator (`%x1f`)
+    /// format:%H%x1f%aN%x1f%aE%x1f%s%x1f%cI%x1f%b%x1f%(trailers:only,unfold)
+    ///
+    /// The body and trailers are optional.
+    pub(crate) fn from_record(record: &str) -> Result<Self, CommandError> {
+        let record = record.trim_start_matches('\n');
+        let mut parts = record.split('\x1f');
+        let hash = parts.next().filter(|h| !h.is_empty()).ok_or(CommandError::ShellError("Failed to parse commit hash".to_string()))?.to_string();
         let author_name = parts.next().ok_or(CommandError::ShellError("Failed to parse commit author's name".to_string()))?.to_string();
         let author_email = parts.next().ok_or(CommandError::ShellError("Failed to parse commit author's email".to_string()))?.to_string();
         let message = parts.next().ok_or(CommandError::ShellError("Failed to parse commit message".to_string()))?.to_string();
         let date_string = parts.next().ok_or(CommandError::ShellError("Failed to parse commit date".to_string()))?;
-        let date = chrono::DateTime::parse_from_str(date_string, "%Y-%m-%dT%H:%M:%S%z")?.with_timezone(&chrono::Utc);
+        let date = chrono::DateTime::parse_from_str(date_string.trim(), "%Y-%m-%dT%H:%M:%S%z")?.with_timezone(&chrono::Utc);
+        let body = parts.next().unwrap_or_default().trim().to_string();
+        let trailers = parts.next().unwrap_or_default()
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+            .collect();
         let author = Author {
             name: author_name,
             email: author_email
@@ -36,11 +57,79 @@ impl Commit {
             hash,
             message,
             author,
-            date
+            date,
+            body,
+            trailers
         })
     }
 }
 
+impl Commit {
+    /// Version component implied by the commit message, based on Conventional Commits.
+    ///
+    /// Breaking changes (`feat!:`, `fix(api)!:` or a `BREAKING CHANGE:` footer) bump major,
+    /// `feat` bumps minor, `fix` and `perf` bump patch. Other commits are not releasable.
+    pub fn bump_component(&self) -> Option<Component> {
+        let commit_type = self.commit_type()?;
+
+        if self.is_breaking() {
+            return Some(Component::Major)
+        }
+
+        match commit_type.as_str() {
+            "feat" => Some(Component::Minor),
+            "fix" | "perf" => Some(Component::Patch),
+            _ => None
+        }
+    }
+
+    /// Lowercase type of a Conventional Commit (`feat`, `fix`, ...), `None` for other messages
+    pub fn commit_type(&self) -> Option<String> {
+        CONVENTIONAL_COMMIT.captures(&self.message)
+            .and_then(|caps| caps.name("type"))
+            .map(|t| t.as_str().to_lowercase())
+    }
+
+    /// Whether a Conventional Commit introduces a breaking change, marked with `!` in the header
+    /// or a `BREAKING CHANGE:` footer in the body.
+    ///
+    /// The body starts after the subject and a blank line, so the subject itself never counts as a footer.
+    pub fn is_breaking(&self) -> bool {
+        match CONVENTIONAL_COMMIT.captures(&self.message) {
+            Some(caps) => caps.name("breaking").is_some() || BREAKING_CHANGE_FOOTER.is_match(&self.body),
+            None => false
+        }
+    }
+
+    /// Message without the Conventional Commit type, the scope is kept as a prefix (`api: add route`)
+    pub fn description(&self) -> String {
+        match CONVENTIONAL_COMMIT.captures(&self.message) {
+            Some(caps) => {
+                let description = caps.name("description").map(|d| d.as_str()).unwrap_or_default();
+                match caps.name("scope").map(|s| s.as_str()).filter(|s| !s.is_empty()) {
+                    Some(scope) => format!("{}: {}", scope, description),
+                    None => description.to_string()
+                }
+            }
+            None => self.message.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+impl Commit {
+    /// Commit of a test author, the message may hold a body after a blank line
+    pub(crate) fn fixture(message: &str) -> Commit {
+        Self::fixture_with_hash("0ee0b0041380df22675472392ab54ff835b07b48", message)
+    }
+
+    pub(crate) fn fixture_with_hash(hash: &str, message: &str) -> Commit {
+        let (subject, body) = message.split_once("\n\n").unwrap_or((message, ""));
+        let record = format!("{}\x1fKamil Czerwiński\x1fkamil@czerwinski.dev\x1f{}\x1f2022-06-25T20:57:13+02:00\x1f{}", hash, subject, body);
+        Commit::from_record(&record).unwrap()
+    }
+}
+
 impl From<ParseError> for CommandError {
     fn from(e: ParseError) -> Self {
         CommandError::ParseError(format!("Failed to parse date, reason: {:?}", e))
@@ -1,9 +1,10 @@
+use crate::commands::Component;
 use crate::commands::shell::git::Commit;
 
 #[test]
 fn parse_git_line_should_return_commit_object() {
-    let line = "0ee0b0041380df22675472392ab54ff835b07b48;Kamil Czerwiński;kamil@czerwinski.dev;Add 2 test apps;2022-06-25T20:57:13+02:00";
-    let commit = Commit::from_line(line).unwrap();
+    let line = "0ee0b0041380df22675472392ab54ff835b07b48\x1fKamil Czerwiński\x1fkamil@czerwinski.dev\x1fAdd 2 test apps\x1f2022-06-25T20:57:13+02:00";
+    let commit = Commit::from_record(line).unwrap();
 
     assert_eq!(commit.hash, "0ee0b0041380df22675472392ab54ff835b07b48");
     assert_eq!(commit.message, "Add 2 test apps");
@@ -14,8 +15,67 @@ fn parse_git_line_should_return_commit_object() {
 
 #[test]
 fn parse_git_line_should_return_error_when_line_is_invalid() {
-    let line = "0ee0b0041380df22675472392ab54ff835b07b48;Kamil Czerwiński;";
-    let commit = Commit::from_line(line);
+    let line = "0ee0b0041380df22675472392ab54ff835b07b48\x1fKamil Czerwiński\x1f";
+    let commit = Commit::from_record(line);
 
     assert!(commit.is_err());
 }
+
+#[test]
+fn parse_git_record_should_keep_semicolons_body_and_trailers() {
+    let record = "\n0ee0b0041380df22675472392ab54ff835b07b48\x1fKamil Czerwiński\x1fkamil@czerwinski.dev\x1f\
+        fix: parse a;b;c\x1f2022-06-25T20:57:13+02:00\x1fLonger explanation.\n\nRefs: #12\nSigned-off-by: Kamil <kamil@czerwinski.dev>\n\x1f\
+        Refs: #12\nSigned-off-by: Kamil <kamil@czerwinski.dev>\n";
+    let commit = Commit::from_record(record).unwrap();
+
+    assert_eq!(commit.message, "fix: parse a;b;c");
+    assert_eq!(commit.body, "Longer explanation.\n\nRefs: #12\nSigned-off-by: Kamil <kamil@czerwinski.dev>");
+    assert_eq!(commit.trailers, vec![
+        ("Refs".to_string(), "#12".to_string()),
+        ("Signed-off-by".to_string(), "Kamil <kamil@czerwinski.dev>".to_string()),
+    ]);
+}
+
+#[test]
+fn bump_component_should_follow_conventional_commits() {
+    assert_eq!(Commit::fixture("feat: add endpoint").bump_component(), Some(Component::Minor));
+    assert_eq!(Commit::fixture("feat(api): add endpoint").bump_component(), Some(Component::Minor));
+    assert_eq!(Commit::fixture("fix: handle timeout").bump_component(), Some(Component::Patch));
+    assert_eq!(Commit::fixture("perf(db): cache queries").bump_component(), Some(Component::Patch));
+    assert_eq!(Commit::fixture("feat!: drop v1 api").bump_component(), Some(Component::Major));
+    assert_eq!(Commit::fixture("refactor(api)!: rename fields").bump_component(), Some(Component::Major));
+}
+
+#[test]
+fn bump_component_should_ignore_non_releasable_commits() {
+    assert_eq!(Commit::fixture("chore: update deps").bump_component(), None);
+    assert_eq!(Commit::fixture("docs(readme): fix typo").bump_component(), None);
+    assert_eq!(Commit::fixture("Add 2 test apps").bump_component(), None);
+    assert_eq!(Commit::fixture("feature: not conventional").bump_component(), None);
+}
+
+#[test]
+fn description_should_strip_the_conventional_commit_type_and_keep_the_scope() {
+    assert_eq!(Commit::fixture("feat(api)!: add route").description(), "api: add route");
+    assert_eq!(Commit::fixture("fix: handle timeout").description(), "handle timeout");
+    assert_eq!(Commit::fixture("Update readme").description(), "Update readme");
+    assert_eq!(Commit::fixture("Update readme").commit_type(), None);
+    assert_eq!(Commit::fixture("Docs(readme): typo").commit_type(), Some("docs".to_string()));
+}
+
+#[test]
+fn bump_component_should_detect_breaking_change_footer_in_the_body() {
+    let record = "0ee0b0041380df22675472392ab54ff835b07b48\x1fKamil Czerwiński\x1fkamil@czerwinski.dev\x1f\
+        feat: new config\x1f2022-06-25T20:57:13+02:00\x1fBREAKING CHANGE: `format` is required\x1f";
+    let commit = Commit::from_record(record).unwrap();
+
+    assert!(commit.is_breaking());
+    assert_eq!(commit.bump_component(), Some(Component::Major));
+}
+
+#[test]
+fn bump_component_should_not_treat_breaking_change_in_the_subject_as_a_footer() {
+    assert_eq!(Commit::fixture("fix: BREAKING CHANGE: config format").bump_component(), Some(Component::Patch));
+    assert_eq!(Commit::fixture("chore: BREAKING CHANGE: config format").bump_component(), None);
+    assert!(!Commit::fixture("feat: BREAKING-CHANGE: config format").is_breaking());
+}
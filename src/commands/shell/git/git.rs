@@ -1,36 +1,64 @@
+use std::cell::Cell;
 use std::collections::HashMap;
-use log::debug;
+use log::{debug, warn};
 use regex::{Captures, Regex};
 use semver::Version;
 use crate::commands::error::CommandError;
-use crate::commands::shell::git::{Commit, GitProvider, Repo, RepoType, Tag};
-use crate::commands::shell::git_cli::ShellGit;
-use crate::commands::shell::GitCli;
+use crate::commands::shell::git::{Commit, GitProvider, Repo, RepoType, Tag, TagInfo};
+use crate::commands::shell::{FetchMode, GitBackend, GitCli, NativeGit, ShellGit};
+use crate::Config;
+
+/// Remote of the repo, unless `remote` is configured
+pub const DEFAULT_REMOTE: &str = "origin";
 
 pub struct Git {
     git: Box<dyn GitCli>,
-    tag_format: String
+    tag_format: String,
+    fetch: FetchMode,
+    remote: String,
+    fetched: Cell<bool>
 }
 
 impl Git {
-    pub fn init(tag_format: String) -> Git {
-        Self::new(Box::new(ShellGit {}), tag_format)
+    /// Git of the current repo, using the configured backend
+    pub fn init(config: &Config) -> Result<Git, CommandError> {
+        let git: Box<dyn GitCli> = match config.git_backend {
+            GitBackend::Shell => Box::new(ShellGit {}),
+            GitBackend::Native => Box::new(NativeGit::new(".")?)
+        };
+        Ok(Self::new(git, config.format.clone()).with_fetch(config.fetch.clone(), &config.remote))
     }
 
+    /// Git which uses local tags only, see `with_fetch`
     pub fn new(git: Box<dyn GitCli>, tag_format: String) -> Git {
-        Git { git, tag_format }
+        Git { git, tag_format, fetch: FetchMode::Never, remote: DEFAULT_REMOTE.to_string(), fetched: Cell::new(false) }
+    }
+
+    /// Fetch tags of a remote before listing them, the remote is also used to detect the repo and push tags
+    pub fn with_fetch(self, fetch: FetchMode, remote: &str) -> Git {
+        Git { fetch, remote: remote.to_string(), ..self }
+    }
+
+    /// Fetch tags of the remote, unless they are not fetched at all or were already fetched once
+    fn fetch_tags(&self) -> Result<(), CommandError> {
+        match self.fetch {
+            FetchMode::Never => return Ok(()),
+            FetchMode::Once if self.fetched.get() => return Ok(()),
+            _ => {}
+        }
+        debug!("Fetching tags of {}", self.remote);
+        self.remote_url(Some("use --offline to skip fetching"))?;
+        self.git.fetch(&self.remote)?;
+        self.fetched.set(true);
+
+        Ok(())
     }
 
     /// List git tags ordered by version descending
     pub fn get_tags(&self, app_name_filter: Option<String>) -> Result<Vec<Tag>, CommandError> {
+        self.fetch_tags()?;
         let format = self.tag_format.clone();
-        let filter = format
-            .replace("{version}", "*");
-        let app_name = match app_name_filter {
-            None => String::from("*"),
-            Some(name) => name
-        };
-        let filter = filter.replace("{app_name}", &app_name);
+        let filter = self.tag_filter(app_name_filter);
         debug!("git tags filter: {}", filter);
         let raw_output = self.git.get_tags(filter)?;
         let mut tags = Self::parse_tags(raw_output.clone(), format);
@@ -40,18 +68,41 @@ impl Git {
         Ok(tags)
     }
 
+    /// Get commit and creation date of tags, by tag name
+    pub fn get_tag_infos(&self, app_name_filter: Option<String>) -> Result<HashMap<String, TagInfo>, CommandError> {
+        self.fetch_tags()?;
+        let raw_output = self.git.get_tag_refs(self.tag_filter(app_name_filter))?;
+
+        let mut infos = HashMap::new();
+        for line in raw_output.lines() {
+            match TagInfo::from_line(line) {
+                Ok(info) => { infos.insert(info.name.clone(), info); }
+                Err(e) => warn!("Failed to parse tag line \"{}\". Skipping! Reason: {:?}", line, e)
+            }
+        }
+
+        Ok(infos)
+    }
+
+    fn tag_filter(&self, app_name_filter: Option<String>) -> String {
+        let app_name = match app_name_filter {
+            None => String::from("*"),
+            Some(name) => name
+        };
+        self.tag_format
+            .replace("{version}", "*")
+            .replace("{app_name}", &app_name)
+    }
+
     /// List latest versions for each application
     pub fn get_latest_tags(&self) -> Result<Vec<Tag>, CommandError> {
         let mut tags: HashMap<String, Tag> = HashMap::new();
         let mut result: Vec<Tag> = vec![];
         for tag_ref in self.get_tags(None)?.iter() {
             let tag = tag_ref.clone();
-            match tags.get(tag.app_name.as_str()) {
-                None => {
-                    tags.insert(tag.app_name.clone(), tag.clone());
-                    result.push(tag);
-                }
-                Some(_) => {}
+            if !tags.contains_key(tag.app_name.as_str()) {
+                tags.insert(tag.app_name.clone(), tag.clone());
+                result.push(tag);
             }
         }
 
@@ -63,39 +114,61 @@ impl Git {
         debug!("Found {} tags for app {}, tags: {:?}", &tags.len(), app_name, &tags);
         let tag = tags.first();
 
-        Ok(tag.map(|t| t.clone()))
+        Ok(tag.cloned())
+    }
+
+    /// Latest version of an application which is not a pre-release
+    pub fn find_latest_stable_tag(&self, app_name: &str) -> Result<Option<Tag>, CommandError> {
+        let tags = self.get_tags(Some(app_name.to_string()))?;
+
+        Ok(tags.into_iter().find(|tag| tag.version.pre.is_empty()))
     }
 
     pub fn get_config(&self, key: &str) -> Result<String, CommandError> {
         self.git.get_config(key)
     }
 
+    /// Url of the remote, fails with a hint about the `remote` setting when the remote does not exist.
+    ///
+    /// `alternative` is appended to the hint, it tells how to do without the remote in the current operation.
+    fn remote_url(&self, alternative: Option<&str>) -> Result<String, CommandError> {
+        match self.get_config(&format!("remote.{}.url", self.remote)) {
+            Ok(url) if !url.trim().is_empty() => Ok(url.trim_end_matches('\n').to_string()),
+            _ => {
+                let mut message = format!(
+                    "Remote \"{}\" does not exist, set the \"remote\" config or --remote flag to an existing remote",
+                    self.remote
+                );
+                if let Some(alternative) = alternative {
+                    message.push_str(&format!(", or {}", alternative));
+                }
+                Err(CommandError::ShellError(message))
+            }
+        }
+    }
+
+    /// Repo of the configured remote
     pub fn get_repo_info(&self) -> Result<Repo, CommandError> {
-        let repo_url = self.get_config("remote.origin.url")?;
+        let repo_url = self.remote_url(None)?;
         let repo_url = repo_url.as_str();
-        let repo_url = repo_url.strip_suffix("\n").unwrap_or(repo_url);
 
         let ssh_re = Regex::new(r"^git@(?P<provider>[a-zA-Z0-9._-]+):(?P<repo>.*)\.git$").unwrap();
-        let http_re = Regex::new(r"^(https?://)?(?P<provider>[a-zA-Z0-9._-]+)/(?P<repo>.*)\.git$").unwrap();
+        let ssh_url_re = Regex::new(r"^ssh://([^@/]+@)?(?P<provider>[a-zA-Z0-9._-]+)(:[0-9]+)?/(?P<repo>.*)\.git$").unwrap();
+        let http_re = Regex::new(r"^(https?://)?([^@/]+@)?(?P<provider>[a-zA-Z0-9._-]+)(:[0-9]+)?/(?P<repo>.*)\.git$").unwrap();
 
         let (repo_type, caps) = if ssh_re.is_match(repo_url) {
             (RepoType::Ssh, ssh_re.captures(repo_url).unwrap())
+        } else if ssh_url_re.is_match(repo_url) {
+            (RepoType::Ssh, ssh_url_re.captures(repo_url).unwrap())
         } else if http_re.is_match(repo_url) {
             (RepoType::Http, http_re.captures(repo_url).unwrap())
         } else {
-            return Err(CommandError::ParseError(format!("Invalid repo url {}", repo_url).to_string()))
+            return Err(CommandError::ParseError(format!("Invalid repo url {}", repo_url)))
         };
 
-        let provider = match caps.name("provider") {
-            Some(provider) => {
-                match provider.as_str() {
-                    "github.com" => GitProvider::Github,
-                    "gitlab.com" => GitProvider::Gitlab,
-                    "bitbucket.com" => GitProvider::Bitbucket,
-                    _ => GitProvider::Unknown
-                }
-            }
-            None => GitProvider::Unknown
+        let host = match caps.name("provider") {
+            Some(host) => host.as_str().to_string(),
+            None => return Err(CommandError::ParseError("Failed to parse repo host".to_string()))
         };
 
         let repo_name = match caps.name("repo") {
@@ -103,22 +176,85 @@ impl Git {
             None => return Err(CommandError::ParseError("Failed to parse repo name".to_string()))
         };
 
+        // Bitbucket Server serves http clones from /scm/{project}/{repo}.git
+        let (provider, repo_name) = match repo_name.strip_prefix("scm/") {
+            Some(name) if repo_type == RepoType::Http => (GitProvider::BitbucketServer, name.to_string()),
+            _ => (Self::provider(&host), repo_name)
+        };
+
         Ok(Repo {
             git_url: repo_url.to_string(),
+            host,
             provider,
             repo_name,
             repo_type
         })
     }
 
+    fn provider(host: &str) -> GitProvider {
+        match host {
+            "github.com" => GitProvider::Github,
+            "gitlab.com" => GitProvider::Gitlab,
+            "bitbucket.org" => GitProvider::Bitbucket,
+            "codeberg.org" => GitProvider::Gitea,
+            _ if host.starts_with("bitbucket.") => GitProvider::BitbucketServer,
+            _ => GitProvider::Unknown
+        }
+    }
+
     pub fn get_commits(&self, tag: Option<Tag>, dir: &str) -> Result<Vec<Commit>, CommandError> {
-        let tag = tag.map(|t| t.formatted());
-        self.git.get_commits(tag, dir)
+        self.get_commits_between(tag.as_ref(), "HEAD", Some(dir.to_string()))
+    }
+
+    /// Get commits made after a tag up to a given revision, in the application directory if it is given
+    pub fn get_commits_between(&self, from: Option<&Tag>, to: &str, dir: Option<String>) -> Result<Vec<Commit>, CommandError> {
+        self.git.get_commits(from.map(|t| t.formatted()), to, dir)
+    }
+
+    /// Count commits made between two tags, in the application directory if it is given
+    pub fn count_commits(&self, from: Option<&Tag>, to: &Tag, dir: Option<String>) -> Result<usize, CommandError> {
+        self.git.count_commits(from.map(|t| t.formatted()), &to.formatted(), dir)
+    }
+
+    /// Get the hash of the commit HEAD points to
+    pub fn head_commit(&self) -> Result<String, CommandError> {
+        self.git.get_commit_hash("HEAD")
+    }
+
+    /// Create an annotated tag at HEAD with a given message
+    pub fn create_tag(&self, tag: &Tag, message: &str) -> Result<(), CommandError> {
+        self.git.create_tag(&tag.formatted(), message)
+    }
+
+    /// Push tags to the remote, either all of them are pushed or none
+    pub fn push_tags(&self, tags: &[Tag], include_head: bool) -> Result<(), CommandError> {
+        self.remote_url(Some("set \"push = false\" to create the tags without pushing them"))?;
+        let mut refs: Vec<String> = tags.iter().map(|t| format!("refs/tags/{}", t.formatted())).collect();
+        if include_head {
+            refs.insert(0, "HEAD".to_string());
+        }
+        debug!("Pushing {} to {}", refs.join(", "), self.remote);
+        self.git.push(&self.remote, refs)
+    }
+
+    /// Commit files, e.g. an updated changelog and version files
+    pub fn commit_files(&self, paths: &[String], message: &str) -> Result<(), CommandError> {
+        self.git.commit_files(paths.to_vec(), message)
+    }
+
+    /// Move HEAD back to a given commit, used to revert commits made during a failed release
+    pub fn reset(&self, commit: &str) -> Result<(), CommandError> {
+        self.git.reset(commit)
+    }
+
+    /// Delete a local tag
+    pub fn delete_tag(&self, tag: &Tag) -> Result<(), CommandError> {
+        self.git.delete_tag(&tag.formatted())
     }
 
     fn parse_tags(raw_tags: String, format: String) -> Vec<Tag> {
         let regex = format
-            .replace("{version}", "(?P<version>[0-9]+\\.[0-9]+\\.[0-9]+)")
+            .replace("{version}", "(?P<version>[0-9]+\\.[0-9]+\\.[0-9]+(-[0-9a-zA-Z.-]+)?(\\+[0-9a-zA-Z.-]+)?)")
             .replace("{app_name}", "(?P<app_name>[0-9a-zA-Z-_]+)");
         let regex = format!("(?P<raw>{})\\n{{0,1}}", regex);
 
@@ -131,11 +267,8 @@ impl Git {
                 .map(|m| String::from(m.as_str()));
             let version = Self::get_version(&caps);
 
-            match (raw, app_name, version) {
-                (Some(raw), Some(app_name), Some(version)) => {
-                    tags.push(Tag::new(format.clone().as_str(), &raw, version, &app_name));
-                },
-                _ => {}
+            if let (Some(raw), Some(app_name), Some(version)) = (raw, app_name, version) {
+                tags.push(Tag::new(format.clone().as_str(), &raw, version, &app_name));
             }
         }
 
@@ -1,10 +1,13 @@
 use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 
 #[derive(Debug, Clone, Eq, Ord, PartialEq, PartialOrd)]
 pub enum GitProvider {
     Github,
     Gitlab,
     Bitbucket,
+    BitbucketServer,
+    Gitea,
     Unknown
 }
 
@@ -13,7 +16,8 @@ impl GitProvider {
         match self {
             GitProvider::Github => "VEMO_GITHUB_TOKEN".to_string(),
             GitProvider::Gitlab => "VEMO_GITLAB_TOKEN".to_string(),
-            GitProvider::Bitbucket => "VEMO_BITBUCKET_TOKEN".to_string(),
+            GitProvider::Bitbucket | GitProvider::BitbucketServer => "VEMO_BITBUCKET_TOKEN".to_string(),
+            GitProvider::Gitea => "VEMO_GITEA_TOKEN".to_string(),
             GitProvider::Unknown => "".to_string()
         }
     }
@@ -22,7 +26,8 @@ impl GitProvider {
         match self {
             GitProvider::Github => "github.token".to_string(),
             GitProvider::Gitlab => "gitlab.token".to_string(),
-            GitProvider::Bitbucket => "bitbucket.token".to_string(),
+            GitProvider::Bitbucket | GitProvider::BitbucketServer => "bitbucket.token".to_string(),
+            GitProvider::Gitea => "gitea.token".to_string(),
             GitProvider::Unknown => "".to_string()
         }
     }
@@ -34,7 +39,25 @@ impl Display for GitProvider {
             GitProvider::Github => write!(f, "github"),
             GitProvider::Gitlab => write!(f, "gitlab"),
             GitProvider::Bitbucket => write!(f, "bitbucket"),
+            GitProvider::BitbucketServer => write!(f, "bitbucket-server"),
+            GitProvider::Gitea => write!(f, "gitea"),
             GitProvider::Unknown => write!(f, "unknown")
         }
     }
 }
+
+impl FromStr for GitProvider {
+    type Err = String;
+
+    /// Parse a provider type as used in the `[providers]` config table
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "github" => Ok(GitProvider::Github),
+            "gitlab" => Ok(GitProvider::Gitlab),
+            "bitbucket" => Ok(GitProvider::Bitbucket),
+            "bitbucket-server" => Ok(GitProvider::BitbucketServer),
+            "gitea" | "forgejo" => Ok(GitProvider::Gitea),
+            _ => Err(format!("Unknown git provider type \"{}\"", s))
+        }
+    }
+}
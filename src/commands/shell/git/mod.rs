@@ -1,15 +1,27 @@
 mod tag;
+mod tag_info;
 mod commit;
 mod repo;
 mod git_provider;
+#[allow(clippy::module_inception)]
 mod git;
 
 pub use tag::Tag;
-pub use commit::Commit;
+pub use tag_info::TagInfo;
+pub use commit::{Author, Commit};
 pub use repo::Repo;
 pub use repo::RepoType;
 pub use git_provider::GitProvider;
-pub use git::Git;
+pub use git::{Git, DEFAULT_REMOTE};
 
 #[cfg(test)]
 mod commit_test;
+
+#[cfg(test)]
+mod tag_test;
+
+#[cfg(test)]
+mod tag_info_test;
+
+#[cfg(test)]
+mod repo_test;
@@ -1,8 +1,10 @@
+use regex::Regex;
 use crate::commands::shell::git::GitProvider;
 
 #[derive(Debug, Clone, Eq, Ord, PartialEq, PartialOrd)]
 pub struct Repo {
     pub git_url: String,
+    pub host: String,
     pub repo_name: String,
     pub provider: GitProvider,
     pub repo_type: RepoType
@@ -13,3 +15,70 @@ pub enum RepoType {
     Ssh,
     Http
 }
+
+impl Repo {
+    /// Url of the repo page in the web interface of the provider, `None` for unknown providers.
+    ///
+    /// Http remotes keep their scheme and port, ssh remotes are served over https by the host.
+    pub fn web_url(&self) -> Option<String> {
+        let base = self.base_url();
+        match self.provider {
+            GitProvider::Github | GitProvider::Gitlab | GitProvider::Bitbucket | GitProvider::Gitea =>
+                Some(format!("{}/{}", base, self.repo_name)),
+            GitProvider::BitbucketServer => {
+                let (project, repo) = self.repo_name.split_once('/')?;
+                Some(format!("{}/projects/{}/repos/{}", base, project, repo))
+            }
+            GitProvider::Unknown => None
+        }
+    }
+
+    /// Link comparing two revisions, changes made after `from` up to `to`
+    pub fn compare_url(&self, from: &str, to: &str) -> Option<String> {
+        let web_url = self.web_url()?;
+        match self.provider {
+            GitProvider::Gitlab => Some(format!("{}/-/compare/{}...{}", web_url, from, to)),
+            GitProvider::Bitbucket => Some(format!("{}/branches/compare/{}%0D{}", web_url, to, from)),
+            GitProvider::BitbucketServer => Some(format!(
+                "{}/compare/diff?sourceBranch={}&targetBranch={}",
+                web_url, urlencoding::encode(to), urlencoding::encode(from)
+            )),
+            _ => Some(format!("{}/compare/{}...{}", web_url, from, to))
+        }
+    }
+
+    /// Link to the page of a commit
+    pub fn commit_url(&self, hash: &str) -> Option<String> {
+        let web_url = self.web_url()?;
+        match self.provider {
+            GitProvider::Gitlab => Some(format!("{}/-/commit/{}", web_url, hash)),
+            GitProvider::Bitbucket | GitProvider::BitbucketServer => Some(format!("{}/commits/{}", web_url, hash)),
+            _ => Some(format!("{}/commit/{}", web_url, hash))
+        }
+    }
+
+    fn base_url(&self) -> String {
+        let http_re = Regex::new(r"^(?P<scheme>https?://)?([^@/]+@)?(?P<host>[^/]+)").unwrap();
+        match (&self.repo_type, http_re.captures(&self.git_url)) {
+            (RepoType::Http, Some(caps)) => {
+                let scheme = caps.name("scheme").map(|s| s.as_str()).unwrap_or("https://");
+                format!("{}{}", scheme, &caps["host"])
+            }
+            _ => format!("https://{}", self.host)
+        }
+    }
+}
+
+#[cfg(test)]
+impl Repo {
+    /// Repo of a provider cloned over ssh
+    pub(crate) fn fixture(provider: GitProvider, host: &str, repo_name: &str) -> Repo {
+        Repo {
+            git_url: format!("git@{}:{}.git", host, repo_name),
+            host: host.to_string(),
+            repo_name: repo_name.to_string(),
+            provider,
+            repo_type: RepoType::Ssh
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use crate::commands::shell::git::{GitProvider, Repo, RepoType};
+
+fn repo(git_url: &str, host: &str, repo_name: &str, provider: GitProvider, repo_type: RepoType) -> Repo {
+    Repo { git_url: git_url.to_string(), host: host.to_string(), repo_name: repo_name.to_string(), provider, repo_type }
+}
+
+#[test]
+fn github_ssh_repo_should_link_to_https_pages() {
+    let repo = repo("git@github.com:kamilczerw/vemo.git", "github.com", "kamilczerw/vemo", GitProvider::Github, RepoType::Ssh);
+
+    assert_eq!(repo.web_url().unwrap(), "https://github.com/kamilczerw/vemo");
+    assert_eq!(repo.compare_url("gateway/v1.0.0", "gateway/v1.1.0").unwrap(),
+               "https://github.com/kamilczerw/vemo/compare/gateway/v1.0.0...gateway/v1.1.0");
+    assert_eq!(repo.commit_url("0ee0b00").unwrap(), "https://github.com/kamilczerw/vemo/commit/0ee0b00");
+}
+
+#[test]
+fn gitlab_repo_should_use_dash_routes() {
+    let repo = repo("https://gitlab.com/group/sub/vemo.git", "gitlab.com", "group/sub/vemo", GitProvider::Gitlab, RepoType::Http);
+
+    assert_eq!(repo.compare_url("v1.0.0", "HEAD").unwrap(), "https://gitlab.com/group/sub/vemo/-/compare/v1.0.0...HEAD");
+    assert_eq!(repo.commit_url("0ee0b00").unwrap(), "https://gitlab.com/group/sub/vemo/-/commit/0ee0b00");
+}
+
+#[test]
+fn bitbucket_repos_should_use_branches_compare_and_commits_pages() {
+    let cloud = repo("https://kamil@bitbucket.org/kamilczerw/vemo.git", "bitbucket.org", "kamilczerw/vemo", GitProvider::Bitbucket, RepoType::Http);
+    let server = repo("https://git.example.com:8443/scm/proj/vemo.git", "git.example.com", "proj/vemo", GitProvider::BitbucketServer, RepoType::Http);
+
+    assert_eq!(cloud.compare_url("v1.0.0", "v1.1.0").unwrap(), "https://bitbucket.org/kamilczerw/vemo/branches/compare/v1.1.0%0Dv1.0.0");
+    assert_eq!(cloud.commit_url("0ee0b00").unwrap(), "https://bitbucket.org/kamilczerw/vemo/commits/0ee0b00");
+    assert_eq!(server.web_url().unwrap(), "https://git.example.com:8443/projects/proj/repos/vemo");
+    assert_eq!(server.compare_url("app/v1.0.0", "app/v1.1.0").unwrap(),
+               "https://git.example.com:8443/projects/proj/repos/vemo/compare/diff?sourceBranch=app%2Fv1.1.0&targetBranch=app%2Fv1.0.0");
+}
+
+#[test]
+fn gitea_ssh_url_repo_should_drop_the_ssh_port() {
+    let repo = repo("ssh://git@codeberg.org:2222/kamil/vemo.git", "codeberg.org", "kamil/vemo", GitProvider::Gitea, RepoType::Ssh);
+
+    assert_eq!(repo.compare_url("v1.0.0", "v1.1.0").unwrap(), "https://codeberg.org/kamil/vemo/compare/v1.0.0...v1.1.0");
+    assert_eq!(repo.commit_url("0ee0b00").unwrap(), "https://codeberg.org/kamil/vemo/commit/0ee0b00");
+}
+
+#[test]
+fn unknown_provider_should_have_no_links() {
+    let repo = repo("git@git.corp.local:kamil/vemo.git", "git.corp.local", "kamil/vemo", GitProvider::Unknown, RepoType::Ssh);
+
+    assert!(repo.web_url().is_none());
+    assert!(repo.compare_url("v1.0.0", "v1.1.0").is_none());
+}
@@ -1,8 +1,13 @@
+use std::cmp::Ordering;
 use std::fmt::{Display, Formatter};
-use semver::Version;
+use chrono::format::{Item, StrftimeItems};
+use chrono::NaiveDate;
+use regex::{Captures, Regex};
+use semver::{Prerelease, Version};
 use crate::commands::Component;
+use crate::commands::error::CommandError;
 
-#[derive(Debug, Clone, Eq, Ord, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Tag {
     pub format: String,
     pub raw: String,
@@ -27,16 +32,115 @@ impl Tag {
             Component::Patch => Version::new(version.major, version.minor, version.patch + 1),
         };
 
-        self.version = new_version;
-        self.raw = Self::raw_version(&self.format, &self.app_name, &self.version);
+        self.set_version(new_version);
 
         self
     }
 
+    /// Bump the version to the next pre-release with a given identifier (e.g. `rc`).
+    ///
+    /// If the current version is already a pre-release with the same identifier, only the
+    /// pre-release number is incremented (`1.3.0-rc.1` -> `1.3.0-rc.2`). If the identifier differs,
+    /// the numbering starts over (`1.3.0-beta.2` -> `1.3.0-rc.1`). Otherwise the `component` is
+    /// bumped first (`1.2.0` -> `1.3.0-rc.1`).
+    pub fn bump_pre_release(mut self, component: &Component, identifier: &str) -> Result<Self, CommandError> {
+        let current = self.version.clone();
+
+        let (base, number) = if current.pre.is_empty() {
+            (self.clone().bump(component).version, 1)
+        } else {
+            let base = Version::new(current.major, current.minor, current.patch);
+            match current.pre.as_str().split_once('.') {
+                Some((ident, number)) if ident == identifier => {
+                    let number = number.parse::<u64>().map_err(|_| {
+                        CommandError::VersionError(format!("Unable to increment pre-release \"{}\"", current.pre))
+                    })?;
+                    (base, number + 1)
+                }
+                _ => (base, 1)
+            }
+        };
+
+        let mut new_version = base;
+        new_version.pre = Self::pre_release(identifier, number)?;
+
+        if new_version <= current {
+            return Err(CommandError::VersionError(
+                format!("Pre-release {} would not be greater than the current version {}", new_version, current)
+            ))
+        }
+
+        self.set_version(new_version);
+
+        Ok(self)
+    }
+
+    /// Turn the version into the first pre-release with a given identifier (`0.1.0` -> `0.1.0-rc.1`).
+    pub fn with_pre_release(mut self, identifier: &str) -> Result<Self, CommandError> {
+        let mut new_version = self.version.clone();
+        new_version.pre = Self::pre_release(identifier, 1)?;
+        self.set_version(new_version);
+
+        Ok(self)
+    }
+
+    /// Promote a pre-release version to its release version (`1.3.0-rc.2` -> `1.3.0`).
+    pub fn promote(mut self) -> Result<Self, CommandError> {
+        if self.version.pre.is_empty() {
+            return Err(CommandError::VersionError(
+                format!("Version {} is not a pre-release, there is nothing to promote", self.version)
+            ))
+        }
+
+        let version = &self.version;
+        let new_version = Version::new(version.major, version.minor, version.patch);
+        self.set_version(new_version);
+
+        Ok(self)
+    }
+
     pub fn formatted(&self) -> String {
         Self::raw_version(&self.format, &self.app_name, &self.version)
     }
 
+    /// Name of the release of the tag, e.g. `{app_name} {version} ({date})`.
+    ///
+    /// Besides the placeholders of the tag format, `{date}` is replaced with the date of the release
+    /// (`2022-06-25`), and `{date:FORMAT}` with the date in a strftime format, e.g. `{date:%d.%m.%Y}`.
+    pub fn release_name(&self, format: &str, date: NaiveDate) -> String {
+        let re = Regex::new(r"\{date(:(?P<format>[^}]+))?\}").unwrap();
+        let name = re.replace_all(format, |caps: &Captures| {
+            let date_format = caps.name("format").map(|f| f.as_str()).unwrap_or("%Y-%m-%d");
+            if Self::is_date_format(date_format) {
+                date.format(date_format).to_string()
+            } else {
+                caps[0].to_string()
+            }
+        });
+
+        Self::raw_version(&name, &self.app_name, &self.version)
+    }
+
+    /// Whether a strftime format is valid, invalid formats cannot be rendered
+    pub fn is_date_format(format: &str) -> bool {
+        !StrftimeItems::new(format).any(|item| item == Item::Error)
+    }
+
+    fn set_version(&mut self, version: Version) {
+        self.version = version;
+        self.raw = Self::raw_version(&self.format, &self.app_name, &self.version);
+    }
+
+    fn pre_release(identifier: &str, number: u64) -> Result<Prerelease, CommandError> {
+        let valid = !identifier.is_empty() && identifier.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+        if !valid {
+            return Err(CommandError::VersionError(format!("Invalid pre-release identifier \"{}\"", identifier)))
+        }
+
+        Prerelease::new(&format!("{}.{}", identifier, number))
+            .map_err(|e| CommandError::VersionError(format!("Invalid pre-release \"{}\", {}", identifier, e)))
+    }
+
     fn raw_version(format: &str, app_name: &str, version: &Version) -> String {
         format
             .replace("{version}", format!("{}", version).as_str())
@@ -45,14 +149,32 @@ impl Tag {
     }
 }
 
+#[cfg(test)]
+impl Tag {
+    /// Tag of an application in the default tag format
+    pub(crate) fn fixture(app_name: &str, version: &str) -> Tag {
+        Tag::new_with_format(crate::cfg::DEFAULT_FORMAT, app_name, Version::parse(version).unwrap())
+    }
+}
+
 impl Display for Tag {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.formatted())
     }
 }
 
+/// Tags are ordered by application name and then by semver precedence of the version,
+/// so pre-releases come before the release they lead to.
+impl Ord for Tag {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.app_name.cmp(&other.app_name)
+            .then_with(|| self.version.cmp(&other.version))
+            .then_with(|| self.raw.cmp(&other.raw))
+    }
+}
+
 impl PartialOrd for Tag {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.version.cmp(&other.version))
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
     }
 }
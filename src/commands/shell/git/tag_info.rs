@@ -0,0 +1,29 @@
+use chrono::{DateTime, Utc};
+use crate::commands::error::CommandError;
+
+/// Details of a git tag, which are not part of its name
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TagInfo {
+    pub name: String,
+    pub commit: String,
+    pub date: DateTime<Utc>,
+    /// Name of the tagger, lightweight tags have none
+    pub tagger: Option<String>
+}
+
+impl TagInfo {
+    /// Create a new tag info from a git tag line
+    /// format:%(refname:strip=2)%1f%(objectname of the commit)%1f%(creatordate:iso-strict)%1f%(taggername)
+    pub(crate) fn from_line(line: &str) -> Result<Self, CommandError> {
+        let mut parts = line.trim_end_matches('\n').split('\x1f');
+        let name = parts.next().filter(|n| !n.is_empty())
+            .ok_or(CommandError::ParseError("Failed to parse tag name".to_string()))?.to_string();
+        let commit = parts.next().filter(|c| !c.is_empty())
+            .ok_or(CommandError::ParseError("Failed to parse tag commit".to_string()))?.to_string();
+        let date_string = parts.next().ok_or(CommandError::ParseError("Failed to parse tag date".to_string()))?;
+        let date = DateTime::parse_from_rfc3339(date_string)?.with_timezone(&Utc);
+        let tagger = parts.next().filter(|t| !t.is_empty()).map(String::from);
+
+        Ok(Self { name, commit, date, tagger })
+    }
+}
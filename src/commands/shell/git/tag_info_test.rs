@@ -0,0 +1,34 @@
+use crate::commands::shell::git::TagInfo;
+
+#[test]
+fn parse_tag_line_should_return_tag_info_object() {
+    let line = "gateway/v1.0.0\x1f0ee0b0041380df22675472392ab54ff835b07b48\x1f2022-06-25T20:57:13+02:00";
+    let info = TagInfo::from_line(line).unwrap();
+
+    assert_eq!(info.name, "gateway/v1.0.0");
+    assert_eq!(info.commit, "0ee0b0041380df22675472392ab54ff835b07b48");
+    assert_eq!(info.date, chrono::DateTime::parse_from_rfc3339("2022-06-25T20:57:13+02:00").unwrap());
+}
+
+#[test]
+fn parse_tag_line_should_return_error_when_line_is_invalid() {
+    let line = "gateway/v1.0.0\x1f0ee0b0041380df22675472392ab54ff835b07b48";
+
+    assert!(TagInfo::from_line(line).is_err());
+}
+
+#[test]
+fn parse_tag_line_with_tagger_should_return_tag_info_with_tagger() {
+    let line = "gateway/v1.0.0\x1f0ee0b0041380df22675472392ab54ff835b07b48\x1f2022-06-25T20:57:13+02:00\x1fKamil Czerwiński";
+    let info = TagInfo::from_line(line).unwrap();
+
+    assert_eq!(info.tagger, Some("Kamil Czerwiński".to_string()));
+}
+
+#[test]
+fn parse_lightweight_tag_line_should_return_tag_info_without_tagger() {
+    let line = "gateway/v1.0.0\x1f0ee0b0041380df22675472392ab54ff835b07b48\x1f2022-06-25T20:57:13+02:00\x1f";
+    let info = TagInfo::from_line(line).unwrap();
+
+    assert_eq!(info.tagger, None);
+}
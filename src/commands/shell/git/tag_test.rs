@@ -0,0 +1,71 @@
+use semver::Version;
+use crate::commands::Component;
+use crate::commands::shell::git::Tag;
+
+#[test]
+fn bump_pre_release_of_a_release_should_bump_component_and_start_numbering() {
+    let new_tag = Tag::fixture("gateway", "1.2.0").bump_pre_release(&Component::Minor, "rc").unwrap();
+
+    assert_eq!(new_tag.version, Version::parse("1.3.0-rc.1").unwrap());
+    assert_eq!(new_tag.raw, "gateway/v1.3.0-rc.1");
+}
+
+#[test]
+fn bump_pre_release_with_the_same_identifier_should_increment_the_number() {
+    let new_tag = Tag::fixture("gateway", "1.3.0-rc.1").bump_pre_release(&Component::Minor, "rc").unwrap();
+
+    assert_eq!(new_tag.version, Version::parse("1.3.0-rc.2").unwrap());
+    assert_eq!(new_tag.raw, "gateway/v1.3.0-rc.2");
+}
+
+#[test]
+fn bump_pre_release_with_a_different_identifier_should_restart_the_number() {
+    let new_tag = Tag::fixture("gateway", "1.3.0-beta.2").bump_pre_release(&Component::Major, "rc").unwrap();
+
+    assert_eq!(new_tag.version, Version::parse("1.3.0-rc.1").unwrap());
+}
+
+#[test]
+fn bump_pre_release_to_a_lower_identifier_should_return_an_error() {
+    let new_tag = Tag::fixture("gateway", "1.3.0-rc.2").bump_pre_release(&Component::Minor, "alpha");
+
+    assert!(new_tag.is_err());
+}
+
+#[test]
+fn bump_pre_release_with_invalid_identifier_should_return_an_error() {
+    let new_tag = Tag::fixture("gateway", "1.2.0").bump_pre_release(&Component::Minor, "rc.1");
+
+    assert!(new_tag.is_err());
+}
+
+#[test]
+fn promote_should_drop_the_pre_release() {
+    let new_tag = Tag::fixture("gateway", "1.3.0-rc.2").promote().unwrap();
+
+    assert_eq!(new_tag.version, Version::parse("1.3.0").unwrap());
+    assert_eq!(new_tag.raw, "gateway/v1.3.0");
+}
+
+#[test]
+fn promote_of_a_release_should_return_an_error() {
+    assert!(Tag::fixture("gateway", "1.3.0").promote().is_err());
+}
+
+#[test]
+fn tags_should_be_ordered_by_semver_precedence() {
+    let mut tags = [Tag::fixture("gateway", "1.3.0"), Tag::fixture("gateway", "1.3.0-rc.2"), Tag::fixture("gateway", "1.2.0"), Tag::fixture("gateway", "1.3.0-rc.10"), Tag::fixture("gateway", "1.3.0-beta.1")];
+    tags.sort();
+
+    let versions: Vec<String> = tags.iter().map(|t| t.version.to_string()).collect();
+    assert_eq!(versions, vec!["1.2.0", "1.3.0-beta.1", "1.3.0-rc.2", "1.3.0-rc.10", "1.3.0"]);
+}
+
+#[test]
+fn release_name_should_replace_tag_and_date_placeholders() {
+    let date = chrono::NaiveDate::from_ymd(2026, 10, 18);
+
+    assert_eq!(Tag::fixture("gateway", "2.4.0").release_name("{app_name} - v{version}", date), "gateway - v2.4.0");
+    assert_eq!(Tag::fixture("gateway", "2.4.0").release_name("Gateway {version} ({date})", date), "Gateway 2.4.0 (2026-10-18)");
+    assert_eq!(Tag::fixture("gateway", "2.4.0").release_name("{version} of {date:%d.%m.%Y}", date), "2.4.0 of 18.10.2026");
+}
@@ -8,50 +8,117 @@ pub struct ShellGit {}
 
 impl GitCli for ShellGit {
 
+    fn fetch(&self, remote: &str) -> Result<(), CommandError> {
+        Self::run(vec!["fetch", remote, "--tags"]).map(|_| ())
+    }
+
     /// List git tags ordered by version descending
     fn get_tags(&self, filter: String) -> Result<String, CommandError> {
-        Self::fetch()?;
         Self::run(vec!["tag", "-l", filter.as_str(), "--sort=-v:refname"])
     }
 
+    fn get_tag_refs(&self, filter: String) -> Result<String, CommandError> {
+        // %1f - unit separator, it cannot be part of a tag name
+        // *objectname - commit of an annotated tag, objectname - commit of a lightweight tag
+        // taggername - empty for a lightweight tag
+        let format = "--format=%(refname:strip=2)%1f\
+            %(if)%(*objectname)%(then)%(*objectname)%(else)%(objectname)%(end)%1f\
+            %(creatordate:iso-strict)%1f\
+            %(taggername)";
+        Self::run(vec!["tag", "-l", filter.as_str(), format])
+    }
+
     fn get_config(&self, key: &str) -> Result<String, CommandError> {
         Self::run(vec!["config", "--get", key])
     }
 
-    fn get_commits(&self, tag: Option<String>, dir: &str) -> Result<Vec<Commit>, CommandError> {
+    fn get_commits(&self, from: Option<String>, to: &str, dir: Option<String>) -> Result<Vec<Commit>, CommandError> {
         // %aN - Author name
         // %aE - Author email
         // %s - Subject
         // %H - Hash
         // %cI - Commit date ISO8601
-        let format = "--pretty=format:%H;%aN;%aE;%s;%cI";
-        let tags = match tag {
-            None => String::from(""),
-            Some(t) => format!("{}..HEAD", t)
+        // %b - Body
+        // %(trailers:only,unfold) - Trailers, one per line
+        // %x1f - unit separator between fields, commits are separated with NUL (-z),
+        // neither can be part of a commit message
+        let format = "--pretty=format:%H%x1f%aN%x1f%aE%x1f%s%x1f%cI%x1f%b%x1f%(trailers:only,unfold)";
+        let range = match from {
+            Some(from) => format!("{}..{}", from, to),
+            None => to.to_string()
         };
-        let git_command = vec![
+        let mut git_command = vec![
             "log",
-            "--oneline",
-            "--decorate",
+            "-z",
             format,
-            &tags,
-            "--",
-            dir
+            range.as_str()
         ];
+        if let Some(dir) = &dir {
+            git_command.extend(["--", dir.as_str()]);
+        }
 
         Self::run(git_command).map(|output| {
             let mut commits = vec![];
-            for line in output.lines() {
-                Commit::from_line(line).map(|c| {
-                    commits.push(c);
-                }).map_err(|e| {
-                    warn!("Failed to parse commit line. Skipping!");
-                    debug!("Reason: {:?}", e);
-                }).expect("TODO: panic message");
+            for record in output.split('\0').filter(|record| !record.trim().is_empty()) {
+                match Commit::from_record(record) {
+                    Ok(commit) => commits.push(commit),
+                    Err(e) => {
+                        warn!("Failed to parse commit \"{}\". Skipping!", record.split('\x1f').next().unwrap_or_default());
+                        debug!("Reason: {:?}", e);
+                    }
+                }
             }
             commits
         })
     }
+
+    fn count_commits(&self, from: Option<String>, to: &str, dir: Option<String>) -> Result<usize, CommandError> {
+        let range = match from {
+            Some(from) => format!("{}..{}", from, to),
+            None => to.to_string()
+        };
+        let mut git_command = vec!["rev-list", "--count", range.as_str()];
+        if let Some(dir) = &dir {
+            git_command.extend(["--", dir.as_str()]);
+        }
+
+        let output = Self::run(git_command)?;
+        output.trim().parse::<usize>()
+            .map_err(|e| CommandError::ParseError(format!("Failed to parse commit count \"{}\", {}", output.trim(), e)))
+    }
+
+    fn get_commit_hash(&self, rev: &str) -> Result<String, CommandError> {
+        Self::run(vec!["rev-parse", "--verify", rev]).map(|hash| hash.trim().to_string())
+    }
+
+    fn create_tag(&self, tag: &str, message: &str) -> Result<(), CommandError> {
+        // Keep the message verbatim, otherwise git would strip markdown headers as comments
+        Self::run(vec!["tag", "-a", tag, "--cleanup=verbatim", "-m", message]).map(|_| ())
+    }
+
+    fn push(&self, remote: &str, refs: Vec<String>) -> Result<(), CommandError> {
+        let mut git_command = vec!["push", "--atomic", remote];
+        git_command.extend(refs.iter().map(String::as_str));
+        Self::run(git_command).map(|_| ())
+    }
+
+    fn commit_files(&self, paths: Vec<String>, message: &str) -> Result<(), CommandError> {
+        let mut git_add = vec!["add", "--"];
+        git_add.extend(paths.iter().map(String::as_str));
+        Self::run(git_add)?;
+
+        let mut git_commit = vec!["commit", "-m", message, "--"];
+        git_commit.extend(paths.iter().map(String::as_str));
+        Self::run(git_commit).map(|_| ())
+    }
+
+    fn reset(&self, rev: &str) -> Result<(), CommandError> {
+        Self::run(vec!["reset", "--keep", rev]).map(|_| ())
+    }
+
+    fn delete_tag(&self, tag: &str) -> Result<(), CommandError> {
+        Self::run(vec!["tag", "-d", tag]).map(|_| ())
+    }
 }
 
 impl ShellGit {
@@ -62,7 +129,7 @@ impl ShellGit {
 
         let output = command
             .output()
-            .expect("Failed to execute git command");
+            .map_err(|e| CommandError::ShellError(format!("Failed to execute git command, {}", e)))?;
 
         if !output.status.success() {
             let shell_error = String::from_utf8(output.stderr)?;
@@ -71,8 +138,4 @@ impl ShellGit {
             Ok(String::from_utf8(output.stdout)?)
         }
     }
-
-    fn fetch() -> Result<(), CommandError> {
-        Self::run(vec!["fetch", "--all", "--tags"]).map(|_| ())
-    }
 }
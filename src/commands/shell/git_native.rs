@@ -0,0 +1,216 @@
+use std::collections::HashSet;
+use std::path::Path;
+use chrono::{FixedOffset, TimeZone, Utc};
+use git2::build::CheckoutBuilder;
+use git2::{ObjectType, Oid, Repository, ResetType, Sort, Time};
+use log::warn;
+use crate::commands::error::CommandError;
+use crate::commands::shell::GitCli;
+use crate::commands::shell::git::{Author, Commit};
+use crate::commands::shell::git_cli::ShellGit;
+
+/// Git backend reading and writing the repo in-process with libgit2.
+///
+/// Fetching and pushing still run the `git` binary, so they use the credentials configured for it.
+pub struct NativeGit {
+    repo: Repository
+}
+
+impl NativeGit {
+    /// Backend of the repo containing a given path
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<NativeGit, CommandError> {
+        Ok(NativeGit { repo: Repository::discover(path)? })
+    }
+
+    /// Commits reachable from `to` but not from `from`, newest first, optionally only those touching a directory.
+    ///
+    /// The directory history is simplified like `git log -- <dir>`: a merge which kept the directory of one
+    /// of its parents is left out together with the history of its other parents.
+    fn walk(&self, from: Option<String>, to: &str, dir: Option<String>) -> Result<Vec<Oid>, CommandError> {
+        let repo = &self.repo;
+        let mut revwalk = repo.revwalk()?;
+        revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+        let to = repo.revparse_single(to)?.peel_to_commit()?.id();
+        revwalk.push(to)?;
+        if let Some(from) = from {
+            revwalk.hide(repo.revparse_single(&from)?.peel_to_commit()?.id())?;
+        }
+        let commits = revwalk.collect::<Result<Vec<Oid>, git2::Error>>()?;
+
+        let dir = dir
+            .map(|dir| dir.trim_start_matches("./").trim_end_matches('/').to_string())
+            .filter(|dir| !dir.is_empty() && dir != ".");
+        let dir = match dir {
+            Some(dir) => dir,
+            None => return Ok(commits)
+        };
+
+        let entry_id = |tree: &git2::Tree| tree.get_path(Path::new(&dir)).ok().map(|entry| entry.id());
+        let range: HashSet<Oid> = commits.iter().cloned().collect();
+        let mut touching: HashSet<Oid> = HashSet::new();
+        let mut visited: HashSet<Oid> = HashSet::new();
+        let mut pending = vec![to];
+        while let Some(oid) = pending.pop() {
+            if !range.contains(&oid) || !visited.insert(oid) {
+                continue
+            }
+            let commit = repo.find_commit(oid)?;
+            let current = entry_id(&commit.tree()?);
+            let mut same_parent = None;
+            for parent in commit.parents() {
+                if entry_id(&parent.tree()?) == current {
+                    same_parent = Some(parent.id());
+                    break
+                }
+            }
+            match same_parent {
+                // Only the parent the directory came from is followed
+                Some(parent) => pending.push(parent),
+                None if commit.parent_count() == 0 && current.is_none() => {}
+                None => {
+                    touching.insert(oid);
+                    pending.extend(commit.parent_ids());
+                }
+            }
+        }
+
+        Ok(commits.into_iter().filter(|oid| touching.contains(oid)).collect())
+    }
+
+    fn commit(&self, oid: Oid) -> Result<Commit, CommandError> {
+        let commit = self.repo.find_commit(oid)?;
+        let message = String::from_utf8_lossy(commit.message_bytes()).to_string();
+        let trailers = git2::message_trailers_strs(&message)
+            .map(|trailers| trailers.iter().map(|(key, value)| (key.to_string(), value.to_string())).collect())
+            .unwrap_or_default();
+        let author = commit.author();
+        let date = Self::date(&commit.committer().when())?.with_timezone(&Utc);
+
+        Ok(Commit {
+            hash: oid.to_string(),
+            message: commit.summary().unwrap_or_default().to_string(),
+            author: Author {
+                name: author.name().unwrap_or_default().to_string(),
+                email: author.email().unwrap_or_default().to_string()
+            },
+            date,
+            body: commit.body().unwrap_or_default().trim().to_string(),
+            trailers
+        })
+    }
+
+    fn date(time: &Time) -> Result<chrono::DateTime<FixedOffset>, CommandError> {
+        FixedOffset::east_opt(time.offset_minutes() * 60)
+            .and_then(|offset| offset.timestamp_opt(time.seconds(), 0).single())
+            .ok_or_else(|| CommandError::ParseError(format!("Invalid git time {}", time.seconds())))
+    }
+}
+
+impl GitCli for NativeGit {
+
+    fn fetch(&self, remote: &str) -> Result<(), CommandError> {
+        ShellGit {}.fetch(remote)
+    }
+
+    fn get_tags(&self, filter: String) -> Result<String, CommandError> {
+        let names = self.repo.tag_names(Some(&filter))?;
+        Ok(names.iter().flatten().collect::<Vec<&str>>().join("\n"))
+    }
+
+    fn get_tag_refs(&self, filter: String) -> Result<String, CommandError> {
+        let repo = &self.repo;
+        let mut lines = vec![];
+        for name in repo.tag_names(Some(&filter))?.iter().flatten() {
+            let object = repo.revparse_single(&format!("refs/tags/{}", name))?;
+            let commit = match object.peel_to_commit() {
+                Ok(commit) => commit,
+                Err(e) => {
+                    warn!("Tag {} does not point to a commit. Skipping! Reason: {}", name, e.message());
+                    continue
+                }
+            };
+            // Annotated tags are dated by the tagger, lightweight tags by their commit, like %(creatordate)
+            let (time, tagger) = match object.as_tag().and_then(|tag| tag.tagger()) {
+                Some(tagger) => (tagger.when(), tagger.name().unwrap_or_default().to_string()),
+                None => (commit.committer().when(), String::new())
+            };
+            lines.push(format!("{}\x1f{}\x1f{}\x1f{}", name, commit.id(), Self::date(&time)?.to_rfc3339(), tagger));
+        }
+
+        Ok(lines.join("\n"))
+    }
+
+    fn get_config(&self, key: &str) -> Result<String, CommandError> {
+        Ok(self.repo.config()?.get_string(key)?)
+    }
+
+    fn get_commits(&self, from: Option<String>, to: &str, dir: Option<String>) -> Result<Vec<Commit>, CommandError> {
+        self.walk(from, to, dir)?.into_iter()
+            .map(|oid| self.commit(oid))
+            .collect()
+    }
+
+    fn count_commits(&self, from: Option<String>, to: &str, dir: Option<String>) -> Result<usize, CommandError> {
+        Ok(self.walk(from, to, dir)?.len())
+    }
+
+    fn get_commit_hash(&self, rev: &str) -> Result<String, CommandError> {
+        Ok(self.repo.revparse_single(rev)?.peel_to_commit()?.id().to_string())
+    }
+
+    fn create_tag(&self, tag: &str, message: &str) -> Result<(), CommandError> {
+        let repo = &self.repo;
+        let head = repo.head()?.peel(ObjectType::Commit)?;
+        repo.tag(tag, &head, &repo.signature()?, message, false)?;
+        Ok(())
+    }
+
+    fn push(&self, remote: &str, refs: Vec<String>) -> Result<(), CommandError> {
+        ShellGit {}.push(remote, refs)
+    }
+
+    fn commit_files(&self, paths: Vec<String>, message: &str) -> Result<(), CommandError> {
+        let repo = &self.repo;
+        // The index is cached by the repository, pick up changes made on disk since it was read
+        let mut index = repo.index()?;
+        index.read(false)?;
+        for path in &paths {
+            index.add_path(Path::new(path))?;
+        }
+        index.write()?;
+
+        // Only the given paths are committed, other staged changes stay in the index
+        let parent = repo.head()?.peel_to_commit()?;
+        let mut tree_index = git2::Index::new()?;
+        tree_index.read_tree(&parent.tree()?)?;
+        for path in &paths {
+            let entry = index.get_path(Path::new(path), 0)
+                .ok_or_else(|| CommandError::FileError(format!("File {} is not in the index", path)))?;
+            tree_index.add(&entry)?;
+        }
+        let tree = repo.find_tree(tree_index.write_tree_to(repo)?)?;
+        let signature = repo.signature()?;
+        repo.commit(Some("HEAD"), &signature, &signature, message, &tree, &[&parent])?;
+
+        Ok(())
+    }
+
+    fn reset(&self, rev: &str) -> Result<(), CommandError> {
+        let repo = &self.repo;
+        let target = repo.revparse_single(rev)?.peel(ObjectType::Commit)?;
+        // Safe checkout fails instead of overwriting local changes, like `git reset --keep`
+        repo.checkout_tree(&target, Some(CheckoutBuilder::new().safe()))?;
+        repo.reset(&target, ResetType::Soft, None)?;
+        Ok(())
+    }
+
+    fn delete_tag(&self, tag: &str) -> Result<(), CommandError> {
+        Ok(self.repo.tag_delete(tag)?)
+    }
+}
+
+impl From<git2::Error> for CommandError {
+    fn from(e: git2::Error) -> Self {
+        CommandError::ShellError(e.message().to_string())
+    }
+}
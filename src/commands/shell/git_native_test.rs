@@ -0,0 +1,165 @@
+use std::fs;
+use std::path::Path;
+use git2::{Oid, Repository, ResetType, Signature, Time};
+use tempfile::TempDir;
+use crate::cfg::DEFAULT_FORMAT;
+use crate::commands::shell::git::{Git, Tag};
+use crate::commands::shell::NativeGit;
+
+/// Temporary repo with a commit in `gateway`, a commit in `api` and a commit in both directories
+fn repo() -> (TempDir, Repository) {
+    let dir = TempDir::new().unwrap();
+    let repo = Repository::init(dir.path()).unwrap();
+    let mut config = repo.config().unwrap();
+    config.set_str("user.name", "Kamil Czerwiński").unwrap();
+    config.set_str("user.email", "kamil@czerwinski.dev").unwrap();
+
+    commit(&repo, &["gateway/main.rs"], "feat(gateway): add route", 1656183433);
+    commit(&repo, &["api/main.rs"], "fix(api): handle timeout\n\nLonger explanation.\n\nRefs: #12\n", 1656183533);
+    commit(&repo, &["gateway/lib.rs", "api/lib.rs"], "refactor!: rename config keys", 1656183633);
+
+    (dir, repo)
+}
+
+fn commit(repo: &Repository, paths: &[&str], message: &str, time: i64) {
+    let root = repo.workdir().unwrap().to_path_buf();
+    let mut index = repo.index().unwrap();
+    for path in paths {
+        let file = root.join(path);
+        fs::create_dir_all(file.parent().unwrap()).unwrap();
+        fs::write(&file, message).unwrap();
+        index.add_path(Path::new(path)).unwrap();
+    }
+    index.write().unwrap();
+
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let signature = Signature::new("Kamil Czerwiński", "kamil@czerwinski.dev", &Time::new(time, 120)).unwrap();
+    let parent = repo.head().ok().map(|head| head.peel_to_commit().unwrap());
+    let parents: Vec<&git2::Commit> = parent.iter().collect();
+    repo.commit(Some("HEAD"), &signature, &signature, message, &tree, &parents).unwrap();
+}
+
+/// Commit on a new branch starting at a given commit, HEAD stays on the current branch
+fn branch_commit(repo: &Repository, branch: &str, start: Oid, paths: &[&str], message: &str, time: i64) -> Oid {
+    let current = repo.head().unwrap().name().unwrap().to_string();
+    let branch = repo.branch(branch, &repo.find_commit(start).unwrap(), false).unwrap();
+    repo.set_head(branch.get().name().unwrap()).unwrap();
+    reset_hard(repo);
+    commit(repo, paths, message, time);
+    let oid = repo.head().unwrap().target().unwrap();
+    repo.set_head(&current).unwrap();
+    reset_hard(repo);
+    oid
+}
+
+/// Merge a commit into HEAD, keeping only the content of HEAD with `ours`, like `git merge -s ours`
+fn merge(repo: &Repository, other: Oid, ours: bool, message: &str, time: i64) {
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    let other = repo.find_commit(other).unwrap();
+    let tree = if ours {
+        head.tree().unwrap()
+    } else {
+        let mut index = repo.merge_commits(&head, &other, None).unwrap();
+        repo.find_tree(index.write_tree_to(repo).unwrap()).unwrap()
+    };
+    let signature = Signature::new("Kamil Czerwiński", "kamil@czerwinski.dev", &Time::new(time, 120)).unwrap();
+    repo.commit(Some("HEAD"), &signature, &signature, message, &tree, &[&head, &other]).unwrap();
+    reset_hard(repo);
+}
+
+fn reset_hard(repo: &Repository) {
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    repo.reset(head.as_object(), ResetType::Hard, None).unwrap();
+}
+
+#[test]
+fn get_commits_should_walk_history_of_a_directory() {
+    let (dir, _repo) = repo();
+    let git = Git::new(Box::new(NativeGit::new(dir.path()).unwrap()), DEFAULT_FORMAT.to_string());
+
+    let commits = git.get_commits_between(None, "HEAD", Some("api".to_string())).unwrap();
+
+    let messages: Vec<&str> = commits.iter().map(|c| c.message.as_str()).collect();
+    assert_eq!(messages, vec!["refactor!: rename config keys", "fix(api): handle timeout"]);
+    assert_eq!(commits[1].body, "Longer explanation.\n\nRefs: #12");
+    assert_eq!(commits[1].trailers, vec![("Refs".to_string(), "#12".to_string())]);
+    assert_eq!(commits[1].author.name, "Kamil Czerwiński");
+    assert_eq!(commits[1].date.to_rfc3339(), "2022-06-25T18:58:53+00:00");
+}
+
+#[test]
+fn get_commits_should_simplify_merges_like_git_log() {
+    let (dir, repo) = repo();
+    let base = repo.head().unwrap().target().unwrap();
+    let merged = branch_commit(&repo, "merged", base, &["gateway/side.rs"], "fix(gateway): merged change", 1656183733);
+    commit(&repo, &["api/side.rs"], "fix(api): change on main", 1656183833);
+    merge(&repo, merged, false, "Merge branch 'merged'", 1656183933);
+    let head = repo.head().unwrap().target().unwrap();
+    let discarded = branch_commit(&repo, "discarded", head, &["gateway/dropped.rs"], "feat(gateway): discarded change", 1656184033);
+    merge(&repo, discarded, true, "Merge branch 'discarded'", 1656184133);
+    let git = Git::new(Box::new(NativeGit::new(dir.path()).unwrap()), DEFAULT_FORMAT.to_string());
+
+    let commits = git.get_commits_between(None, "HEAD", Some("gateway".to_string())).unwrap();
+
+    // Same as `git log --format=%s -- gateway`
+    let messages: Vec<&str> = commits.iter().map(|c| c.message.as_str()).collect();
+    assert_eq!(messages, vec!["fix(gateway): merged change", "refactor!: rename config keys", "feat(gateway): add route"]);
+    assert_eq!(git.get_commits_between(None, "HEAD", None).unwrap().len(), 8);
+}
+
+#[test]
+fn create_tag_should_tag_head_and_list_it_with_its_commit() {
+    let (dir, repo) = repo();
+    let git = Git::new(Box::new(NativeGit::new(dir.path()).unwrap()), DEFAULT_FORMAT.to_string());
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    repo.tag_lightweight("gateway/v1.0.0", head.parent(0).unwrap().as_object(), false).unwrap();
+
+    git.create_tag(&Tag::fixture("gateway", "1.1.0"), "## What's Changed").unwrap();
+
+    let tags = git.get_tags(Some("gateway".to_string())).unwrap();
+    assert_eq!(tags.iter().map(|t| t.raw.as_str()).collect::<Vec<&str>>(), vec!["gateway/v1.1.0", "gateway/v1.0.0"]);
+    let infos = git.get_tag_infos(Some("gateway".to_string())).unwrap();
+    assert_eq!(infos["gateway/v1.1.0"].commit, head.id().to_string());
+    assert_eq!(infos["gateway/v1.1.0"].tagger, Some("Kamil Czerwiński".to_string()));
+    assert_eq!(infos["gateway/v1.0.0"].tagger, None);
+    assert_eq!(git.count_commits(Some(&Tag::fixture("gateway", "1.0.0")), &Tag::fixture("gateway", "1.1.0"), Some("gateway".to_string())).unwrap(), 1);
+}
+
+#[test]
+fn get_tag_infos_should_skip_tags_which_do_not_point_to_a_commit() {
+    let (dir, repo) = repo();
+    let git = Git::new(Box::new(NativeGit::new(dir.path()).unwrap()), DEFAULT_FORMAT.to_string());
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    repo.tag_lightweight("gateway/v1.0.0", head.as_object(), false).unwrap();
+    repo.tag_lightweight("gateway/v1.1.0", head.tree().unwrap().as_object(), false).unwrap();
+
+    let infos = git.get_tag_infos(Some("gateway".to_string())).unwrap();
+
+    assert_eq!(infos.len(), 1);
+    assert_eq!(infos["gateway/v1.0.0"].commit, head.id().to_string());
+}
+
+#[test]
+fn commit_files_should_commit_only_given_paths_and_reset_should_revert_them() {
+    let (dir, repo) = repo();
+    let git = Git::new(Box::new(NativeGit::new(dir.path()).unwrap()), DEFAULT_FORMAT.to_string());
+    let original_head = git.head_commit().unwrap();
+    fs::write(dir.path().join("gateway/CHANGELOG.md"), "# Changelog\n").unwrap();
+    fs::write(dir.path().join("api/main.rs"), "staged").unwrap();
+    let mut index = repo.index().unwrap();
+    index.add_path(Path::new("api/main.rs")).unwrap();
+    index.write().unwrap();
+
+    git.commit_files(&["gateway/CHANGELOG.md".to_string()], "chore(release): gateway v1.1.0").unwrap();
+
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    assert_eq!(head.summary(), Some("chore(release): gateway v1.1.0"));
+    assert!(head.tree().unwrap().get_path(Path::new("gateway/CHANGELOG.md")).is_ok());
+    assert_eq!(git.get_commits_between(None, "HEAD", Some("api".to_string())).unwrap().len(), 2);
+
+    git.reset(&original_head).unwrap();
+
+    assert_eq!(git.head_commit().unwrap(), original_head);
+    assert!(!dir.path().join("gateway/CHANGELOG.md").exists());
+    assert_eq!(fs::read_to_string(dir.path().join("api/main.rs")).unwrap(), "staged");
+}
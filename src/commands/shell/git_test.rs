@@ -1,114 +1,107 @@
-use semver::Version;
+use crate::cfg::DEFAULT_FORMAT;
 use crate::commands::error::CommandError;
-use crate::commands::shell::git::{Commit, Git, GitProvider, RepoType, Tag};
-use crate::commands::shell::GitCli;
-use mockall::*;
+use crate::commands::shell::git::{Git, GitProvider, RepoType, Tag};
+use crate::commands::shell::{FetchMode, MockGitCli};
 use mockall::predicate::*;
 
-// TODO: use automock to mock GitCli
-struct  ValidGitCli {}
-impl GitCli for ValidGitCli {
-    fn get_tags(&self, _filter: String) -> Result<String, CommandError> {
-        Ok(format!(
-            "app/v0.1.0\n\
+fn tags_mock() -> MockGitCli {
+    let mut mock = MockGitCli::new();
+    mock.expect_get_tags()
+        .returning(|_| Ok("app/v0.1.0\n\
             gateway/v0.0.1\n\
             app/v0.1.1\n\
             app/v1.0.0\n\
-            gateway/v1.0.0"
-        ))
-    }
-
-    fn get_config(&self, _key: &str) -> Result<String, CommandError> {
-        Ok("git@github.com:kamilczerw/vemo.git".to_string())
-    }
-
-    fn get_commits(&self, tag: Option<String>, dir: &str) -> Result<Vec<Commit>, CommandError> {
-        todo!()
-    }
+            gateway/v1.0.0".to_string()));
+    mock
 }
 
-static TAG_FORMAT: &str = "{app_name}/v{version}";
-
 #[test]
 fn get_tags_should_extract_tags_sorted_by_version_descending() {
-    let git = Git::new(Box::new(ValidGitCli {}), "{app_name}/v{version}".to_string());
+    let git = Git::new(Box::new(tags_mock()), DEFAULT_FORMAT.to_string());
     let tags = git.get_tags(None).unwrap();
 
     assert_eq!(tags.len(), 5);
-    assert_eq!(tags[0], Tag::new(TAG_FORMAT, "gateway/v1.0.0", Version::parse("1.0.0").unwrap(), "gateway"));
-    assert_eq!(tags[1], Tag::new(TAG_FORMAT, "gateway/v0.0.1", Version::parse("0.0.1").unwrap(), "gateway"));
-    assert_eq!(tags[2], Tag::new(TAG_FORMAT, "app/v1.0.0", Version::parse("1.0.0").unwrap(), "app"));
-    assert_eq!(tags[3], Tag::new(TAG_FORMAT, "app/v0.1.1", Version::parse("0.1.1").unwrap(), "app"));
-    assert_eq!(tags[4], Tag::new(TAG_FORMAT, "app/v0.1.0", Version::parse("0.1.0").unwrap(), "app"));
+    assert_eq!(tags[0], Tag::fixture("gateway", "1.0.0"));
+    assert_eq!(tags[1], Tag::fixture("gateway", "0.0.1"));
+    assert_eq!(tags[2], Tag::fixture("app", "1.0.0"));
+    assert_eq!(tags[3], Tag::fixture("app", "0.1.1"));
+    assert_eq!(tags[4], Tag::fixture("app", "0.1.0"));
 }
 
 #[test]
 fn get_latest_tags_should_extract_only_latest_tags_for_all_apps() {
-    let git = Git::new(Box::new(ValidGitCli {}), TAG_FORMAT.to_string());
+    let git = Git::new(Box::new(tags_mock()), DEFAULT_FORMAT.to_string());
     let tags = git.get_latest_tags().unwrap();
 
     assert_eq!(tags.len(), 2);
-    assert_eq!(tags[0], Tag::new(TAG_FORMAT, "gateway/v1.0.0", Version::parse("1.0.0").unwrap(), "gateway"));
-    assert_eq!(tags[1], Tag::new(TAG_FORMAT, "app/v1.0.0", Version::parse("1.0.0").unwrap(), "app"));
+    assert_eq!(tags[0], Tag::fixture("gateway", "1.0.0"));
+    assert_eq!(tags[1], Tag::fixture("app", "1.0.0"));
 }
 
 #[test]
 fn get_latest_tag_for_specific_app_should_return_a_tag() {
-    let git = Git::new(Box::new(ValidGitCli {}), TAG_FORMAT.to_string());
+    let git = Git::new(Box::new(tags_mock()), DEFAULT_FORMAT.to_string());
     let tag = git.find_latest_tag("gateway").unwrap();
 
-    assert_eq!(tag, Some(Tag::new(TAG_FORMAT, "gateway/v1.0.0", Version::parse("1.0.0").unwrap(), "gateway")));
+    assert_eq!(tag, Some(Tag::fixture("gateway", "1.0.0")));
 }
 
-mock! {
-    GC {}
+#[test]
+fn get_tags_should_parse_pre_release_tags_sorted_by_precedence() {
+    let mut mock = MockGitCli::new();
+    mock.expect_get_tags()
+        .returning(|_| Ok("gateway/v1.2.0\ngateway/v1.3.0-rc.1\ngateway/v1.3.0-rc.2".to_string()));
 
-    impl GitCli for GC {
-        fn get_config(&self, _key: &str) -> Result<String, CommandError>;
-        fn get_tags(&self, _filter: String) -> Result<String, CommandError>;
-        fn get_commits(&self, _tag: Option<String>, _dir: &str) -> Result<Vec<Commit>, CommandError>;
-    }
+    let git = Git::new(Box::new(mock), DEFAULT_FORMAT.to_string());
+    let tags = git.get_tags(None).unwrap();
+
+    assert_eq!(tags.len(), 3);
+    assert_eq!(tags[0], Tag::fixture("gateway", "1.3.0-rc.2"));
+    assert_eq!(tags[1], Tag::fixture("gateway", "1.3.0-rc.1"));
+    assert_eq!(tags[2], Tag::fixture("gateway", "1.2.0"));
 }
 
 #[test]
 fn get_repo_info_with_valid_github_ssh_url_should_return_a_repo_info() {
-    let mut mock = MockGC::new();
+    let mut mock = MockGitCli::new();
     mock.expect_get_config()
         .returning(|_| Ok("git@github.com:kamilczerw/vemo.git".to_string()));
 
-    let git = Git::new(Box::new(mock), TAG_FORMAT.to_string());
+    let git = Git::new(Box::new(mock), DEFAULT_FORMAT.to_string());
 
     let repo_info = git.get_repo_info().unwrap();
 
     assert_eq!(repo_info.repo_name, "kamilczerw/vemo");
     assert_eq!(repo_info.git_url, "git@github.com:kamilczerw/vemo.git".to_string());
+    assert_eq!(repo_info.host, "github.com");
     assert_eq!(repo_info.repo_type, RepoType::Ssh);
     assert_eq!(repo_info.provider, GitProvider::Github);
 }
 
 #[test]
 fn get_repo_info_with_valid_github_ssh_url_with_new_line_at_the_end_should_return_a_repo_info() {
-    let mut mock = MockGC::new();
+    let mut mock = MockGitCli::new();
     mock.expect_get_config()
         .returning(|_| Ok("git@github.com:kamilczerw/vemo.git\n".to_string()));
 
-    let git = Git::new(Box::new(mock), TAG_FORMAT.to_string());
+    let git = Git::new(Box::new(mock), DEFAULT_FORMAT.to_string());
 
     let repo_info = git.get_repo_info().unwrap();
 
     assert_eq!(repo_info.repo_name, "kamilczerw/vemo");
     assert_eq!(repo_info.git_url, "git@github.com:kamilczerw/vemo.git".to_string());
+    assert_eq!(repo_info.host, "github.com");
     assert_eq!(repo_info.repo_type, RepoType::Ssh);
     assert_eq!(repo_info.provider, GitProvider::Github);
 }
 
 #[test]
 fn get_repo_info_with_valid_github_http_url_should_return_a_repo_info() {
-    let mut mock = MockGC::new();
+    let mut mock = MockGitCli::new();
     mock.expect_get_config()
         .returning(|_| Ok("https://github.com/kamilczerw/vemo.git".to_string()));
 
-    let git = Git::new(Box::new(mock), TAG_FORMAT.to_string());
+    let git = Git::new(Box::new(mock), DEFAULT_FORMAT.to_string());
 
     let repo_info = git.get_repo_info().unwrap();
 
@@ -120,11 +113,11 @@ fn get_repo_info_with_valid_github_http_url_should_return_a_repo_info() {
 
 #[test]
 fn get_repo_info_with_valid_github_http_url_but_skipping_protocol_should_return_a_repo_info() {
-    let mut mock = MockGC::new();
+    let mut mock = MockGitCli::new();
     mock.expect_get_config()
         .returning(|_| Ok("github.com/kamilczerw/vemo.git".to_string()));
 
-    let git = Git::new(Box::new(mock), TAG_FORMAT.to_string());
+    let git = Git::new(Box::new(mock), DEFAULT_FORMAT.to_string());
 
     let repo_info = git.get_repo_info().unwrap();
 
@@ -136,11 +129,11 @@ fn get_repo_info_with_valid_github_http_url_but_skipping_protocol_should_return_
 
 #[test]
 fn get_repo_info_with_invalid_http_url_should_return_a_failure() {
-    let mut mock = MockGC::new();
+    let mut mock = MockGitCli::new();
     mock.expect_get_config()
         .returning(|_| Ok("test://github.com/kamilczerw/vemo.git".to_string()));
 
-    let git = Git::new(Box::new(mock), TAG_FORMAT.to_string());
+    let git = Git::new(Box::new(mock), DEFAULT_FORMAT.to_string());
 
     let repo_info = git.get_repo_info();
     assert!(repo_info.is_err());
@@ -148,11 +141,11 @@ fn get_repo_info_with_invalid_http_url_should_return_a_failure() {
 
 #[test]
 fn get_repo_info_with_invalid_ssh_url_should_return_a_failure() {
-    let mut mock = MockGC::new();
+    let mut mock = MockGitCli::new();
     mock.expect_get_config()
         .returning(|_| Ok("ssh@github.com:kamilczerw/vemo.git".to_string()));
 
-    let git = Git::new(Box::new(mock), TAG_FORMAT.to_string());
+    let git = Git::new(Box::new(mock), DEFAULT_FORMAT.to_string());
 
     let repo_info = git.get_repo_info();
     assert!(repo_info.is_err());
@@ -160,11 +153,11 @@ fn get_repo_info_with_invalid_ssh_url_should_return_a_failure() {
 
 #[test]
 fn get_repo_info_with_invalid_provider_should_return_a_repo_info_with_unknown_provider() {
-    let mut mock = MockGC::new();
+    let mut mock = MockGitCli::new();
     mock.expect_get_config()
         .returning(|_| Ok("git@invalid.com:kamilczerw/vemo.git".to_string()));
 
-    let git = Git::new(Box::new(mock), TAG_FORMAT.to_string());
+    let git = Git::new(Box::new(mock), DEFAULT_FORMAT.to_string());
 
     let repo_info = git.get_repo_info().unwrap();
 
@@ -173,3 +166,210 @@ fn get_repo_info_with_invalid_provider_should_return_a_repo_info_with_unknown_pr
     assert_eq!(repo_info.repo_type, RepoType::Ssh);
     assert_eq!(repo_info.provider, GitProvider::Unknown);
 }
+
+#[test]
+fn create_tag_should_create_an_annotated_tag_with_formatted_name() {
+    let mut mock = MockGitCli::new();
+    mock.expect_create_tag()
+        .with(eq("gateway/v1.3.0"), eq("## What's Changed"))
+        .times(1)
+        .returning(|_, _| Ok(()));
+
+    let git = Git::new(Box::new(mock), DEFAULT_FORMAT.to_string());
+    let tag = Tag::fixture("gateway", "1.3.0");
+
+    assert!(git.create_tag(&tag, "## What's Changed").is_ok());
+}
+
+#[test]
+fn push_tags_should_push_formatted_tags_to_the_configured_remote() {
+    let mut mock = MockGitCli::new();
+    mock.expect_get_config()
+        .with(eq("remote.upstream.url"))
+        .returning(|_| Ok("git@github.com:kamilczerw/vemo.git\n".to_string()));
+    mock.expect_push()
+        .with(eq("upstream"), eq(vec!["refs/tags/gateway/v1.3.0".to_string(), "refs/tags/app/v0.2.0".to_string()]))
+        .times(1)
+        .returning(|_, _| Ok(()));
+
+    let git = Git::new(Box::new(mock), DEFAULT_FORMAT.to_string()).with_fetch(FetchMode::Never, "upstream");
+    let tags = vec![
+        Tag::fixture("gateway", "1.3.0"),
+        Tag::fixture("app", "0.2.0")
+    ];
+
+    assert!(git.push_tags(&tags, false).is_ok());
+}
+
+#[test]
+fn push_tags_with_head_should_push_the_current_branch_with_tags() {
+    let mut mock = MockGitCli::new();
+    mock.expect_get_config()
+        .returning(|_| Ok("git@github.com:kamilczerw/vemo.git\n".to_string()));
+    mock.expect_push()
+        .with(eq("origin"), eq(vec!["HEAD".to_string(), "refs/tags/gateway/v1.3.0".to_string()]))
+        .times(1)
+        .returning(|_, _| Ok(()));
+
+    let git = Git::new(Box::new(mock), DEFAULT_FORMAT.to_string());
+    let tags = vec![Tag::fixture("gateway", "1.3.0")];
+
+    assert!(git.push_tags(&tags, true).is_ok());
+}
+
+#[test]
+fn get_repo_info_with_valid_gitlab_url_with_subgroups_should_return_a_repo_info() {
+    let mut mock = MockGitCli::new();
+    mock.expect_get_config()
+        .returning(|_| Ok("git@gitlab.com:kamilczerw/tools/vemo.git".to_string()));
+
+    let git = Git::new(Box::new(mock), DEFAULT_FORMAT.to_string());
+
+    let repo_info = git.get_repo_info().unwrap();
+
+    assert_eq!(repo_info.repo_name, "kamilczerw/tools/vemo");
+    assert_eq!(repo_info.host, "gitlab.com");
+    assert_eq!(repo_info.provider, GitProvider::Gitlab);
+}
+
+#[test]
+fn get_repo_info_with_bitbucket_cloud_http_url_with_user_should_return_a_repo_info() {
+    let mut mock = MockGitCli::new();
+    mock.expect_get_config()
+        .returning(|_| Ok("https://kamil@bitbucket.org/kamilczerw/vemo.git".to_string()));
+
+    let git = Git::new(Box::new(mock), DEFAULT_FORMAT.to_string());
+
+    let repo_info = git.get_repo_info().unwrap();
+
+    assert_eq!(repo_info.repo_name, "kamilczerw/vemo");
+    assert_eq!(repo_info.host, "bitbucket.org");
+    assert_eq!(repo_info.repo_type, RepoType::Http);
+    assert_eq!(repo_info.provider, GitProvider::Bitbucket);
+}
+
+#[test]
+fn get_repo_info_with_bitbucket_server_http_url_should_return_a_repo_info() {
+    let mut mock = MockGitCli::new();
+    mock.expect_get_config()
+        .returning(|_| Ok("https://git.example.com:8443/scm/vemo/vemo.git".to_string()));
+
+    let git = Git::new(Box::new(mock), DEFAULT_FORMAT.to_string());
+
+    let repo_info = git.get_repo_info().unwrap();
+
+    assert_eq!(repo_info.repo_name, "vemo/vemo");
+    assert_eq!(repo_info.host, "git.example.com");
+    assert_eq!(repo_info.repo_type, RepoType::Http);
+    assert_eq!(repo_info.provider, GitProvider::BitbucketServer);
+}
+
+#[test]
+fn get_repo_info_with_bitbucket_server_ssh_url_should_return_a_repo_info() {
+    let mut mock = MockGitCli::new();
+    mock.expect_get_config()
+        .returning(|_| Ok("ssh://git@bitbucket.example.com:7999/vemo/vemo.git".to_string()));
+
+    let git = Git::new(Box::new(mock), DEFAULT_FORMAT.to_string());
+
+    let repo_info = git.get_repo_info().unwrap();
+
+    assert_eq!(repo_info.repo_name, "vemo/vemo");
+    assert_eq!(repo_info.host, "bitbucket.example.com");
+    assert_eq!(repo_info.repo_type, RepoType::Ssh);
+    assert_eq!(repo_info.provider, GitProvider::BitbucketServer);
+}
+
+#[test]
+fn get_tag_infos_should_return_tag_details_by_tag_name() {
+    let mut mock = MockGitCli::new();
+    mock.expect_get_tag_refs()
+        .with(eq("gateway/v*".to_string()))
+        .returning(|_| Ok("gateway/v1.0.0\x1f0ee0b0041380df22675472392ab54ff835b07b48\x1f2022-06-25T20:57:13+02:00\n\
+            invalid line\n".to_string()));
+
+    let git = Git::new(Box::new(mock), DEFAULT_FORMAT.to_string());
+    let infos = git.get_tag_infos(Some("gateway".to_string())).unwrap();
+
+    assert_eq!(infos.len(), 1);
+    let info = infos.get("gateway/v1.0.0").unwrap();
+    assert_eq!(info.commit, "0ee0b0041380df22675472392ab54ff835b07b48");
+    assert_eq!(info.date.to_rfc3339(), "2022-06-25T18:57:13+00:00");
+}
+
+#[test]
+fn get_tags_with_fetch_once_should_fetch_the_remote_only_before_the_first_listing() {
+    let mut mock = MockGitCli::new();
+    mock.expect_get_config()
+        .returning(|_| Ok("git@github.com:kamilczerw/vemo.git\n".to_string()));
+    mock.expect_fetch()
+        .with(eq("upstream"))
+        .times(1)
+        .returning(|_| Ok(()));
+    mock.expect_get_tags()
+        .times(2)
+        .returning(|_| Ok("gateway/v1.2.0".to_string()));
+
+    let git = Git::new(Box::new(mock), DEFAULT_FORMAT.to_string()).with_fetch(FetchMode::Once, "upstream");
+
+    assert_eq!(git.get_tags(None).unwrap().len(), 1);
+    assert_eq!(git.get_tags(None).unwrap().len(), 1);
+}
+
+#[test]
+fn get_tags_with_fetch_always_should_fetch_before_every_listing() {
+    let mut mock = MockGitCli::new();
+    mock.expect_get_config()
+        .returning(|_| Ok("git@github.com:kamilczerw/vemo.git\n".to_string()));
+    mock.expect_fetch()
+        .times(2)
+        .returning(|_| Ok(()));
+    mock.expect_get_tags()
+        .returning(|_| Ok("gateway/v1.2.0".to_string()));
+
+    let git = Git::new(Box::new(mock), DEFAULT_FORMAT.to_string()).with_fetch(FetchMode::Always, "origin");
+
+    git.get_tags(None).unwrap();
+    git.get_tags(None).unwrap();
+}
+
+#[test]
+fn get_tags_with_fetch_never_should_use_local_tags() {
+    let mut mock = MockGitCli::new();
+    mock.expect_fetch().never();
+    mock.expect_get_tags()
+        .returning(|_| Ok("gateway/v1.2.0".to_string()));
+
+    let git = Git::new(Box::new(mock), DEFAULT_FORMAT.to_string()).with_fetch(FetchMode::Never, "origin");
+
+    assert_eq!(git.get_tags(None).unwrap().len(), 1);
+}
+
+#[test]
+fn missing_remote_should_fail_with_a_hint_before_fetching_or_pushing() {
+    let mut mock = MockGitCli::new();
+    mock.expect_get_config()
+        .with(eq("remote.upstream.url"))
+        .returning(|_| Err(CommandError::ShellError("".to_string())));
+    mock.expect_fetch().never();
+    mock.expect_push().never();
+
+    let git = Git::new(Box::new(mock), DEFAULT_FORMAT.to_string()).with_fetch(FetchMode::Once, "upstream");
+    let tags = vec![Tag::fixture("gateway", "1.3.0")];
+
+    match git.get_tags(None) {
+        Err(CommandError::ShellError(message)) => {
+            assert!(message.starts_with("Remote \"upstream\" does not exist"), "{}", message);
+            assert!(message.contains("--offline"), "{}", message);
+        }
+        other => panic!("Unexpected result {:?}", other)
+    }
+    assert!(git.get_repo_info().is_err());
+    match git.push_tags(&tags, false) {
+        Err(CommandError::ShellError(message)) => {
+            assert!(message.contains("push = false"), "{}", message);
+            assert!(!message.contains("--offline"), "{}", message);
+        }
+        other => panic!("Unexpected result {:?}", other)
+    }
+}
@@ -1,22 +1,124 @@
 mod git_cli;
+mod git_native;
 pub(crate) mod git;
 
 #[cfg(test)]
 mod git_test;
 
+#[cfg(test)]
+mod git_native_test;
+
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 use mockall::*;
 use mockall::predicate::*;
 use crate::commands::error::CommandError;
 use crate::commands::shell::git::Commit;
 
+pub use git_cli::ShellGit;
+pub use git_native::NativeGit;
+
+/// Implementation of git operations, configured with `git_backend`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum GitBackend {
+    /// Run the `git` binary
+    #[default]
+    Shell,
+    /// Use libgit2 in-process, fetching and pushing still run the `git` binary
+    Native
+}
+
+impl Display for GitBackend {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            GitBackend::Shell => write!(f, "shell"),
+            GitBackend::Native => write!(f, "native")
+        }
+    }
+}
+
+/// When tags of the remote are fetched, configured with `fetch` or `--offline`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum FetchMode {
+    /// Use local tags only
+    Never,
+    /// Fetch before the first listing of tags in a run
+    #[default]
+    Once,
+    /// Fetch before every listing of tags
+    Always
+}
+
+impl Display for FetchMode {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            FetchMode::Never => write!(f, "never"),
+            FetchMode::Once => write!(f, "once"),
+            FetchMode::Always => write!(f, "always")
+        }
+    }
+}
+
+impl FromStr for FetchMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "never" => Ok(FetchMode::Never),
+            "once" => Ok(FetchMode::Once),
+            "always" => Ok(FetchMode::Always),
+            _ => Err(format!("Unknown fetch mode \"{}\", expected one of: never, once, always", s))
+        }
+    }
+}
+
+impl FromStr for GitBackend {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "shell" => Ok(GitBackend::Shell),
+            "native" => Ok(GitBackend::Native),
+            _ => Err(format!("Unknown git backend \"{}\", expected one of: shell, native", s))
+        }
+    }
+}
+
 #[automock]
 pub trait GitCli {
+    /// Fetch tags of a given remote
+    fn fetch(&self, remote: &str) -> Result<(), CommandError>;
+
     /// Get filtered git tags
     fn get_tags(&self, filter: String) -> Result<String, CommandError>;
 
+    /// Get details of filtered git tags, one tag per line
+    fn get_tag_refs(&self, filter: String) -> Result<String, CommandError>;
+
     /// Get git configuration for a given key
     fn get_config(&self, key: &str) -> Result<String, CommandError>;
 
-    /// Get git commits for a given tag and directory
-    fn get_commits(&self, tag: Option<String>, dir: &str) -> Result<Vec<Commit>, CommandError>;
+    /// Get git commits reachable from `to` but not from `from`, optionally only those touching a directory
+    fn get_commits(&self, from: Option<String>, to: &str, dir: Option<String>) -> Result<Vec<Commit>, CommandError>;
+
+    /// Count commits reachable from `to` but not from `from`, optionally only those touching a directory
+    fn count_commits(&self, from: Option<String>, to: &str, dir: Option<String>) -> Result<usize, CommandError>;
+
+    /// Get the commit hash a given revision points to
+    fn get_commit_hash(&self, rev: &str) -> Result<String, CommandError>;
+
+    /// Create an annotated tag pointing at HEAD
+    fn create_tag(&self, tag: &str, message: &str) -> Result<(), CommandError>;
+
+    /// Push refs to a given remote, either all of them are pushed or none
+    fn push(&self, remote: &str, refs: Vec<String>) -> Result<(), CommandError>;
+
+    /// Commit files with a given message, other staged changes are not committed
+    fn commit_files(&self, paths: Vec<String>, message: &str) -> Result<(), CommandError>;
+
+    /// Move HEAD back to a given commit, keeping local changes which are not part of the reverted commits
+    fn reset(&self, rev: &str) -> Result<(), CommandError>;
+
+    /// Delete a local tag
+    fn delete_tag(&self, tag: &str) -> Result<(), CommandError>;
 }
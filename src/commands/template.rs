@@ -0,0 +1,126 @@
+use std::fs;
+use chrono::NaiveDate;
+use handlebars::{no_escape, Handlebars};
+use serde_json::{json, Value};
+use crate::commands::error::CommandError;
+use crate::commands::shell::git::{Commit, Repo, Tag};
+
+/// Conventional Commit types with a title of their group, in the order groups are listed
+const GROUPS: [(&str, &str); 9] = [
+    ("feat", "Features"),
+    ("fix", "Bug Fixes"),
+    ("perf", "Performance Improvements"),
+    ("refactor", "Code Refactoring"),
+    ("revert", "Reverts"),
+    ("docs", "Documentation"),
+    ("test", "Tests"),
+    ("build", "Build System"),
+    ("ci", "Continuous Integration"),
+];
+
+/// Release body template, inline in the config or in a file relative to the root of the repo
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReleaseTemplate {
+    Inline(String),
+    File(String)
+}
+
+impl ReleaseTemplate {
+    fn source(&self) -> Result<String, CommandError> {
+        match self {
+            ReleaseTemplate::Inline(template) => Ok(template.clone()),
+            ReleaseTemplate::File(path) => fs::read_to_string(path)
+                .map_err(|e| CommandError::FileError(format!("Unable to read release template {}, {}", path, e)))
+        }
+    }
+}
+
+/// Values available in a release body template.
+///
+/// `commits` are listed as they come from git, `groups` contain the same commits grouped by their
+/// Conventional Commit type (commits of other types are in the `other` group) and `breaking_changes`
+/// lists the commits introducing a breaking change. Commits link to their page in the web interface
+/// of the `repo`, when it is known.
+pub(crate) fn context(app_name: &str, previous: Option<&Tag>, tag: Option<&Tag>, date: NaiveDate, commits: &[Commit],
+                      repo: Option<&Repo>, compare_url: Option<String>) -> Value {
+    let commit_context = |commit: &Commit| commit_context(commit, repo);
+
+    let mut types: Vec<String> = GROUPS.iter().map(|(t, _)| t.to_string()).collect();
+    for commit in commits {
+        if let Some(commit_type) = commit.commit_type().filter(|t| !types.contains(t)) {
+            types.push(commit_type);
+        }
+    }
+    types.push("other".to_string());
+
+    let groups: Vec<Value> = types.iter()
+        .map(|commit_type| {
+            let commits: Vec<Value> = commits.iter()
+                .filter(|commit| commit.commit_type().unwrap_or_else(|| "other".to_string()) == *commit_type)
+                .map(&commit_context)
+                .collect();
+            (commit_type, commits)
+        })
+        .filter(|(_, commits)| !commits.is_empty())
+        .map(|(commit_type, commits)| json!({
+            "type": commit_type,
+            "title": group_title(commit_type),
+            "commits": commits,
+        }))
+        .collect();
+
+    json!({
+        "app_name": app_name,
+        "version": tag.map(|t| t.version.to_string()),
+        "tag": tag.map(|t| t.formatted()),
+        "previous_version": previous.map(|t| t.version.to_string()),
+        "previous_tag": previous.map(|t| t.formatted()),
+        "date": date.format("%Y-%m-%d").to_string(),
+        "compare_url": compare_url,
+        "repo_url": repo.and_then(|r| r.web_url()),
+        "commits": commits.iter().map(&commit_context).collect::<Vec<Value>>(),
+        "groups": groups,
+        "breaking_changes": commits.iter().filter(|c| c.is_breaking()).map(&commit_context).collect::<Vec<Value>>(),
+    })
+}
+
+fn commit_context(commit: &Commit, repo: Option<&Repo>) -> Value {
+    json!({
+        "hash": commit.hash,
+        "short_hash": commit.hash.chars().take(7).collect::<String>(),
+        "url": repo.and_then(|r| r.commit_url(&commit.hash)),
+        "subject": commit.message,
+        "body": commit.body,
+        "trailers": commit.trailers.iter()
+            .map(|(key, value)| json!({ "key": key, "value": value }))
+            .collect::<Vec<Value>>(),
+        "description": commit.description(),
+        "type": commit.commit_type(),
+        "breaking": commit.is_breaking(),
+        "author": {
+            "name": commit.author.name,
+            "email": commit.author.email,
+        },
+        "date": commit.date.to_rfc3339(),
+    })
+}
+
+fn group_title(commit_type: &str) -> String {
+    match GROUPS.iter().find(|(t, _)| *t == commit_type) {
+        Some((_, title)) => title.to_string(),
+        None if commit_type == "other" => "Other Changes".to_string(),
+        None => {
+            let mut chars = commit_type.chars();
+            chars.next().map(|c| c.to_uppercase().chain(chars).collect()).unwrap_or_default()
+        }
+    }
+}
+
+/// Render a release body, values are not escaped since the body is markdown
+pub(crate) fn render(template: &ReleaseTemplate, context: &Value) -> Result<String, CommandError> {
+    let mut handlebars = Handlebars::new();
+    handlebars.register_escape_fn(no_escape);
+
+    handlebars.render_template(&template.source()?, context)
+        .map_err(|e| CommandError::TemplateError(format!("Failed to render release template, {}", e)))
+}
@@ -0,0 +1,59 @@
+use chrono::NaiveDate;
+use crate::commands::shell::git::{Commit, GitProvider, Repo, Tag};
+use crate::commands::template::{context, render, ReleaseTemplate};
+
+#[test]
+fn context_should_group_commits_by_type() {
+    let commits = vec![
+        Commit::fixture_with_hash("0ee0b0041380df22675472392ab54ff835b07b48", "fix: handle timeout"),
+        Commit::fixture_with_hash("1ee0b0041380df22675472392ab54ff835b07b48", "feat(api)!: add route"),
+        Commit::fixture_with_hash("2ee0b0041380df22675472392ab54ff835b07b48", "style: format"),
+        Commit::fixture_with_hash("3ee0b0041380df22675472392ab54ff835b07b48", "Update readme"),
+    ];
+    let (previous, new) = (Tag::fixture("gateway", "1.2.0"), Tag::fixture("gateway", "2.0.0"));
+
+    let context = context("gateway", Some(&previous), Some(&new), NaiveDate::from_ymd(2022, 6, 25), &commits, None, None);
+
+    assert_eq!(context["version"], "2.0.0");
+    assert_eq!(context["previous_tag"], "gateway/v1.2.0");
+    assert_eq!(context["date"], "2022-06-25");
+    let titles: Vec<&str> = context["groups"].as_array().unwrap().iter().map(|g| g["title"].as_str().unwrap()).collect();
+    assert_eq!(titles, vec!["Features", "Bug Fixes", "Style", "Other Changes"]);
+    assert_eq!(context["groups"][0]["commits"][0]["short_hash"], "1ee0b00");
+    assert_eq!(context["breaking_changes"][0]["description"], "api: add route");
+}
+
+#[test]
+fn render_should_not_escape_markdown() {
+    let commits = vec![Commit::fixture_with_hash("0ee0b0041380df22675472392ab54ff835b07b48", "fix: handle <timeout> & retry")];
+    let new = Tag::fixture("gateway", "1.2.1");
+    let context = context("gateway", None, Some(&new), NaiveDate::from_ymd(2022, 6, 25), &commits, None,
+                          Some("https://github.com/kamilczerw/vemo/compare/a...b".to_string()));
+    let template = ReleaseTemplate::Inline(
+        "# {{app_name}} {{version}} ({{date}})\n{{#each groups}}## {{title}}\n{{#each commits}}- {{description}} ({{short_hash}}) by {{author.name}}\n{{/each}}{{/each}}{{compare_url}}".to_string()
+    );
+
+    let body = render(&template, &context).unwrap();
+
+    assert_eq!(body, "# gateway 1.2.1 (2022-06-25)\n## Bug Fixes\n- handle <timeout> & retry (0ee0b00) by Kamil Czerwiński\n\
+        https://github.com/kamilczerw/vemo/compare/a...b");
+}
+
+#[test]
+fn render_should_fail_for_invalid_template() {
+    let context = context("gateway", None, None, NaiveDate::from_ymd(2022, 6, 25), &[], None, None);
+
+    assert!(render(&ReleaseTemplate::Inline("{{#each commits}}".to_string()), &context).is_err());
+    assert!(render(&ReleaseTemplate::File("/does/not/exist.hbs".to_string()), &context).is_err());
+}
+
+#[test]
+fn context_should_link_commits_to_the_repo() {
+    let commits = vec![Commit::fixture_with_hash("0ee0b0041380df22675472392ab54ff835b07b48", "fix: handle timeout")];
+    let repo = Repo::fixture(GitProvider::Gitlab, "gitlab.com", "group/vemo");
+
+    let context = context("gateway", None, None, NaiveDate::from_ymd(2022, 6, 25), &commits, Some(&repo), None);
+
+    assert_eq!(context["repo_url"], "https://gitlab.com/group/vemo");
+    assert_eq!(context["commits"][0]["url"], "https://gitlab.com/group/vemo/-/commit/0ee0b0041380df22675472392ab54ff835b07b48");
+}
@@ -0,0 +1,172 @@
+use std::fmt::{Display, Formatter};
+use regex::Regex;
+use semver::Version;
+
+/// Format of a file holding the version of an application
+#[derive(Debug, Clone)]
+pub enum VersionFileFormat {
+    /// `version` in the `[package]` (or `[workspace.package]`) table of Cargo.toml
+    Cargo,
+    /// Top-level `version` of package.json
+    Npm,
+    /// `version` in the `[project]` (or `[tool.poetry]`) table of pyproject.toml
+    Pyproject,
+    /// Top-level `version` of a Helm Chart.yaml
+    Helm,
+    /// File containing only the version, e.g. VERSION
+    Plain,
+    /// Custom pattern, the version is its `version` named group or its first group
+    Regex(Regex)
+}
+
+impl VersionFileFormat {
+    /// Parse a format name, `pattern` is required by the `regex` format
+    pub fn parse(format: &str, pattern: Option<String>) -> Result<VersionFileFormat, String> {
+        match (format.to_lowercase().as_str(), pattern) {
+            ("cargo", _) => Ok(VersionFileFormat::Cargo),
+            ("npm", _) => Ok(VersionFileFormat::Npm),
+            ("pyproject", _) => Ok(VersionFileFormat::Pyproject),
+            ("helm", _) => Ok(VersionFileFormat::Helm),
+            ("plain", _) => Ok(VersionFileFormat::Plain),
+            ("regex", Some(pattern)) => {
+                let re = Regex::new(&pattern).map_err(|e| format!("Invalid pattern \"{}\", {}", pattern, e))?;
+                if re.captures_len() < 2 {
+                    return Err(format!("Pattern \"{}\" has no group capturing the version", pattern))
+                }
+                Ok(VersionFileFormat::Regex(re))
+            }
+            ("regex", None) => Err("Missing \"pattern\" of the regex version file format".to_string()),
+            (other, _) => Err(format!(
+                "Unsupported version file format \"{}\", expected one of: cargo, npm, pyproject, helm, plain, regex", other
+            ))
+        }
+    }
+
+    /// Replace the version in the content of a file, everything else is kept as it is.
+    ///
+    /// Returns `None` when the version cannot be found in the content.
+    pub fn update(&self, content: &str, version: &Version) -> Option<String> {
+        let version = version.to_string();
+        match self {
+            VersionFileFormat::Cargo => toml_version(content, &["package", "workspace.package"], &version),
+            VersionFileFormat::Pyproject => toml_version(content, &["project", "tool.poetry"], &version),
+            VersionFileFormat::Npm => json_version(content, &version),
+            VersionFileFormat::Helm => {
+                let re = Regex::new(r#"(?m)^version:[ \t]*["']?(?P<version>[^"'\s#]+)"#).unwrap();
+                replace_group(content, &re, &version, false)
+            }
+            VersionFileFormat::Plain => {
+                if content.trim().is_empty() {
+                    return None
+                }
+                let trimmed = content.trim();
+                let start = content.find(trimmed).unwrap_or(0);
+                Some(format!("{}{}{}", &content[..start], version, &content[start + trimmed.len()..]))
+            }
+            VersionFileFormat::Regex(re) => replace_group(content, re, &version, true)
+        }
+    }
+}
+
+impl Display for VersionFileFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VersionFileFormat::Cargo => write!(f, "cargo"),
+            VersionFileFormat::Npm => write!(f, "npm"),
+            VersionFileFormat::Pyproject => write!(f, "pyproject"),
+            VersionFileFormat::Helm => write!(f, "helm"),
+            VersionFileFormat::Plain => write!(f, "plain"),
+            VersionFileFormat::Regex(re) => write!(f, "regex({})", re)
+        }
+    }
+}
+
+/// Replace the `version` named group, or the first group, of the first (or every) match
+fn replace_group(content: &str, re: &Regex, version: &str, all: bool) -> Option<String> {
+    let mut spans = vec![];
+    for caps in re.captures_iter(content) {
+        if let Some(group) = caps.name("version").or_else(|| caps.get(1)) {
+            spans.push((group.start(), group.end()));
+        }
+        if !all {
+            break
+        }
+    }
+
+    replace_spans(content, &spans, version)
+}
+
+fn replace_spans(content: &str, spans: &[(usize, usize)], version: &str) -> Option<String> {
+    if spans.is_empty() {
+        return None
+    }
+
+    let mut result = String::with_capacity(content.len());
+    let mut last = 0;
+    for (start, end) in spans {
+        result.push_str(&content[last..*start]);
+        result.push_str(version);
+        last = *end;
+    }
+    result.push_str(&content[last..]);
+
+    Some(result)
+}
+
+/// Replace the `version` key of the first table found, tables are tried in the given order
+fn toml_version(content: &str, tables: &[&str], version: &str) -> Option<String> {
+    let header = Regex::new(r"^\s*\[\s*([^\[\]]+?)\s*\]\s*(#.*)?$").unwrap();
+    let key = Regex::new(r#"^\s*version\s*=\s*["'](?P<version>[^"']*)["']"#).unwrap();
+
+    for table in tables {
+        let mut current: Option<String> = None;
+        let mut offset = 0;
+        for line in content.split_inclusive('\n') {
+            let trimmed = line.trim_end_matches(['\r', '\n']);
+            if trimmed.trim_start().starts_with('[') {
+                current = header.captures(trimmed).map(|caps| caps[1].replace(' ', ""));
+            } else if current.as_deref() == Some(*table) {
+                if let Some(group) = key.captures(trimmed).and_then(|caps| caps.name("version")) {
+                    return replace_spans(content, &[(offset + group.start(), offset + group.end())], version)
+                }
+            }
+            offset += line.len();
+        }
+    }
+
+    None
+}
+
+/// Replace the top-level `version` of a JSON document
+fn json_version(content: &str, version: &str) -> Option<String> {
+    let re = Regex::new(r#""version"\s*:\s*"(?P<version>[^"\\]*)""#).unwrap();
+
+    let span = re.captures_iter(content)
+        .find(|caps| json_depth(&content[..caps.get(0).unwrap().start()]) == Some(1))
+        .and_then(|caps| caps.name("version"))
+        .map(|group| (group.start(), group.end()))?;
+
+    replace_spans(content, &[span], version)
+}
+
+/// Nesting depth at the end of a JSON prefix, `None` if the prefix ends inside a string
+fn json_depth(prefix: &str) -> Option<i32> {
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for c in prefix.chars() {
+        match (in_string, escaped, c) {
+            (true, true, _) => escaped = false,
+            (true, false, '\\') => escaped = true,
+            (true, false, '"') => in_string = false,
+            (true, false, _) => {}
+            (false, _, '"') => in_string = true,
+            (false, _, '{') | (false, _, '[') => depth += 1,
+            (false, _, '}') | (false, _, ']') => depth -= 1,
+            _ => {}
+        }
+    }
+
+    if in_string { None } else { Some(depth) }
+}
@@ -0,0 +1,72 @@
+use semver::Version;
+use crate::commands::version_file::VersionFileFormat;
+
+fn update(format: &str, content: &str) -> Option<String> {
+    VersionFileFormat::parse(format, None).unwrap().update(content, &Version::new(1, 3, 0))
+}
+
+#[test]
+fn cargo_format_should_update_only_the_package_version() {
+    let content = "[package]\nname = \"gateway\" # the name\nversion = \"1.2.0\" # keep me\n\n\
+        [dependencies]\nserde = { version = \"1.0\" }\n\n[dev-dependencies.mockall]\nversion = \"0.11\"\n";
+
+    assert_eq!(update("cargo", content).unwrap(), "[package]\nname = \"gateway\" # the name\nversion = \"1.3.0\" # keep me\n\n\
+        [dependencies]\nserde = { version = \"1.0\" }\n\n[dev-dependencies.mockall]\nversion = \"0.11\"\n");
+}
+
+#[test]
+fn cargo_format_should_fall_back_to_workspace_package() {
+    let content = "[package]\nname = \"gateway\"\nversion.workspace = true\n\n[workspace.package]\nversion = '1.2.0'\n";
+
+    assert_eq!(update("cargo", content).unwrap(), "[package]\nname = \"gateway\"\nversion.workspace = true\n\n[workspace.package]\nversion = '1.3.0'\n");
+}
+
+#[test]
+fn pyproject_format_should_update_project_or_poetry_version() {
+    assert_eq!(update("pyproject", "[project]\nname = \"api\"\nversion = \"1.2.0\"\n").unwrap(),
+               "[project]\nname = \"api\"\nversion = \"1.3.0\"\n");
+    assert_eq!(update("pyproject", "[tool.black]\nversion = \"22\"\n\n[tool.poetry]\nversion = \"1.2.0\"\n").unwrap(),
+               "[tool.black]\nversion = \"22\"\n\n[tool.poetry]\nversion = \"1.3.0\"\n");
+}
+
+#[test]
+fn npm_format_should_update_only_the_top_level_version() {
+    let content = "{\n  \"name\": \"web\",\n  \"engines\": { \"version\": \"18\" },\n  \"description\": \"\\\"version\\\": \\\"x\\\"\",\n  \"version\": \"1.2.0\"\n}\n";
+
+    assert_eq!(update("npm", content).unwrap(),
+               "{\n  \"name\": \"web\",\n  \"engines\": { \"version\": \"18\" },\n  \"description\": \"\\\"version\\\": \\\"x\\\"\",\n  \"version\": \"1.3.0\"\n}\n");
+}
+
+#[test]
+fn helm_format_should_update_the_chart_version_but_not_app_version() {
+    let content = "apiVersion: v2\nname: gateway\nversion: \"1.2.0\" # chart\nappVersion: 1.2.0\n";
+
+    assert_eq!(update("helm", content).unwrap(), "apiVersion: v2\nname: gateway\nversion: \"1.3.0\" # chart\nappVersion: 1.2.0\n");
+}
+
+#[test]
+fn plain_format_should_replace_the_whole_content_and_keep_the_new_line() {
+    assert_eq!(update("plain", "1.2.0\n").unwrap(), "1.3.0\n");
+    assert_eq!(update("plain", ""), None);
+}
+
+#[test]
+fn regex_format_should_replace_the_version_group_of_every_match() {
+    let format = VersionFileFormat::parse("regex", Some(r#"VERSION: &str = "(?P<version>[^"]+)""#.to_string())).unwrap();
+    let content = "pub const VERSION: &str = \"1.2.0\";\n";
+
+    assert_eq!(format.update(content, &Version::new(1, 3, 0)).unwrap(), "pub const VERSION: &str = \"1.3.0\";\n");
+}
+
+#[test]
+fn update_should_return_none_when_version_is_missing() {
+    assert_eq!(update("cargo", "[dependencies]\nversion = \"1.0\"\n"), None);
+    assert_eq!(update("npm", "{ \"dependencies\": { \"version\": \"1.0\" } }"), None);
+}
+
+#[test]
+fn parse_should_fail_for_unknown_format_or_regex_without_pattern() {
+    assert!(VersionFileFormat::parse("gradle", None).is_err());
+    assert!(VersionFileFormat::parse("regex", None).is_err());
+    assert!(VersionFileFormat::parse("regex", Some("no group".to_string())).is_err());
+}
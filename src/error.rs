@@ -2,6 +2,18 @@ use config::ConfigError;
 use crate::commands::error::CommandError;
 use crate::git::client::error::GitClientError;
 
+/// Exit code for all failures without a dedicated code
+pub const EXIT_FAILURE: i32 = 1;
+
+/// Exit code when the git provider rejected the credentials
+pub const EXIT_AUTH_FAILURE: i32 = 3;
+
+/// Exit code when the git provider reported a conflict, e.g. the release already exists
+pub const EXIT_CONFLICT: i32 = 4;
+
+/// Exit code when the git provider could not be reached
+pub const EXIT_NETWORK_FAILURE: i32 = 5;
+
 pub struct AppError {
     pub message: String,
     pub code: i32
@@ -16,24 +28,34 @@ impl From<ConfigError> for AppError {
             _ => format!("{}", err)
         };
 
-        AppError { message, code: 1 }
+        AppError { message, code: EXIT_FAILURE }
     }
 }
 
 impl From<CommandError> for AppError {
     fn from(err: CommandError) -> Self {
         match err {
-            CommandError::ParseError(message) => { AppError { message, code: 1 } }
-            CommandError::ShellError(message) => { AppError { message, code: 1 } }
-            CommandError::GitClientError(e) => { AppError { message: format!("{}", e), code: 1 } }
+            CommandError::ParseError(message) => { AppError { message, code: EXIT_FAILURE } }
+            CommandError::ShellError(message) => { AppError { message, code: EXIT_FAILURE } }
+            CommandError::GitClientError(e) => { AppError::from(e) }
+            CommandError::VersionError(message) => { AppError { message, code: EXIT_FAILURE } }
+            CommandError::FileError(message) => { AppError { message, code: EXIT_FAILURE } }
+            CommandError::TemplateError(message) => { AppError { message, code: EXIT_FAILURE } }
+            CommandError::UsageError(message) => { AppError { message, code: EXIT_FAILURE } }
+            CommandError::PromptError(message) => { AppError { message, code: EXIT_FAILURE } }
         }
-        // TODO: implement better mapping for command errors
-        // AppError { message: format!(""), code: 1 }
     }
 }
 
 impl From<GitClientError> for AppError {
     fn from(err: GitClientError) -> Self {
-        AppError { message: format!("{}", err), code: 1 }
+        let code = match &err {
+            GitClientError::ApiError { status: 401 | 403, .. } => EXIT_AUTH_FAILURE,
+            GitClientError::ApiError { status: 409 | 422, .. } => EXIT_CONFLICT,
+            GitClientError::RequestError(_) => EXIT_NETWORK_FAILURE,
+            _ => EXIT_FAILURE
+        };
+
+        AppError { message: format!("{}", err), code }
     }
 }
@@ -0,0 +1,66 @@
+use mockito::mock;
+use crate::commands::error::CommandError;
+use crate::error::{AppError, EXIT_AUTH_FAILURE, EXIT_CONFLICT, EXIT_FAILURE, EXIT_NETWORK_FAILURE};
+use crate::git::client::error::GitClientError;
+
+/// Error returned by the git provider API for a response with a given status
+fn api_error(status: usize, body: &str) -> CommandError {
+    let path = format!("/exit-code/{}", status);
+    let _server = mock("GET", path.as_str())
+        .with_status(status)
+        .with_body(body)
+        .create();
+
+    let response = reqwest::blocking::get(format!("{}{}", mockito::server_url(), path)).unwrap();
+    match GitClientError::check_status(response) {
+        Err(err) => CommandError::GitClientError(err),
+        Ok(_) => panic!("Expected an API error for status {}", status)
+    }
+}
+
+#[test]
+fn rejected_credentials_should_exit_with_auth_failure_code() {
+    let err = api_error(401, r#"{"message": "Bad credentials"}"#);
+    assert!(matches!(err, CommandError::GitClientError(GitClientError::ApiError { status: 401, .. })));
+
+    let app_error = AppError::from(err);
+
+    assert_eq!(app_error.code, EXIT_AUTH_FAILURE);
+    assert!(app_error.message.contains("Bad credentials"));
+}
+
+#[test]
+fn missing_resource_should_exit_with_generic_failure_code() {
+    let app_error = AppError::from(api_error(404, r#"{"message": "Not Found"}"#));
+
+    assert_eq!(app_error.code, EXIT_FAILURE);
+    assert!(app_error.message.contains("Not Found"));
+}
+
+#[test]
+fn existing_release_should_exit_with_conflict_code() {
+    let body = r#"{"message": "Validation Failed", "errors": [{"code": "already_exists", "field": "tag_name"}]}"#;
+
+    let app_error = AppError::from(api_error(422, body));
+
+    assert_eq!(app_error.code, EXIT_CONFLICT);
+    assert!(app_error.message.contains("already_exists: tag_name"));
+}
+
+#[test]
+fn unreachable_provider_should_exit_with_network_failure_code() {
+    // Nothing listens on the discard port, the connection is refused
+    let err = reqwest::blocking::get("http://127.0.0.1:9").unwrap_err();
+
+    let app_error = AppError::from(CommandError::GitClientError(GitClientError::RequestError(err)));
+
+    assert_eq!(app_error.code, EXIT_NETWORK_FAILURE);
+}
+
+#[test]
+fn command_error_should_exit_with_generic_failure_code() {
+    let app_error = AppError::from(CommandError::UsageError("Application name is missing".to_string()));
+
+    assert_eq!(app_error.code, EXIT_FAILURE);
+    assert_eq!(app_error.message, "Application name is missing");
+}
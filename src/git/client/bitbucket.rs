@@ -0,0 +1,88 @@
+use reqwest::blocking::Client;
+use crate::git::client::error::GitClientError;
+use crate::git::GitClient;
+use crate::commands::shell::git::{GitProvider, Repo, Tag};
+use serde_json::json;
+
+/// Bitbucket has no releases, so the client creates an annotated tag with the release body
+/// as its message. Both Bitbucket Cloud and Bitbucket Server (Data Center) are supported.
+pub struct BitbucketClient {
+    pub token: String,
+    pub username: Option<String>,
+    pub http: Client,
+    pub api: String,
+    pub repo: String,
+    pub provider: GitProvider
+}
+
+impl BitbucketClient {
+    /// Create a new BitbucketClient
+    /// # Arguments
+    /// * `token` - Bitbucket access token, or an app password if `username` is set
+    /// * `username` - Bitbucket username, used for basic authentication
+    /// * `api` - Base url of the Bitbucket API, e.g. https://api.bitbucket.org/2.0
+    /// * `repo` - Repo object
+    pub fn new(token: String, username: Option<String>, api: String, repo: Repo) -> Result<BitbucketClient, GitClientError> {
+        let repo_path = match repo.provider {
+            GitProvider::BitbucketServer => {
+                let (project, slug) = repo.repo_name.rsplit_once('/')
+                    .ok_or_else(|| GitClientError::InvalidRepo(repo.repo_name.clone()))?;
+                format!("projects/{}/repos/{}", project, slug)
+            }
+            _ => format!("repositories/{}", repo.repo_name)
+        };
+
+        Ok(BitbucketClient {
+            token,
+            username,
+            http: Client::new(),
+            api: api.trim_end_matches('/').to_string(),
+            repo: repo_path,
+            provider: repo.provider
+        })
+    }
+}
+
+impl GitClient for BitbucketClient {
+    /// Create a new annotated tag in Bitbucket
+    fn create_release(&self, _name: String, tag: Tag, description: String, target: String) -> Result<(), GitClientError> {
+        let (url, body) = match self.provider {
+            GitProvider::BitbucketServer => (
+                format!("{}/{}/tags", self.api, self.repo),
+                json!({
+                    "name": tag.raw,
+                    "startPoint": target,
+                    "message": description,
+                })
+            ),
+            _ => (
+                format!("{}/{}/refs/tags", self.api, self.repo),
+                json!({
+                    "name": tag.raw,
+                    "target": { "hash": target },
+                    "message": description,
+                })
+            )
+        };
+
+        let request = self.http.post(url)
+            .header("User-Agent", "Vemo-Cli")
+            .header("Content-Type", "application/json")
+            .body(serde_json::to_string(&body).unwrap());
+
+        let request = match &self.username {
+            Some(username) => request.basic_auth(username, Some(&self.token)),
+            None => request.bearer_auth(&self.token)
+        };
+
+        request.send()
+            .map_err(GitClientError::RequestError)
+            .and_then(GitClientError::check_status)?;
+
+        Ok(())
+    }
+
+    fn creates_tag(&self) -> bool {
+        true
+    }
+}
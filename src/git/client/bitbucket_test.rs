@@ -0,0 +1,59 @@
+use mockito::{mock, Matcher};
+use serde_json::json;
+use crate::commands::shell::git::{GitProvider, Repo, Tag};
+use crate::git::client::bitbucket::BitbucketClient;
+use crate::git::GitClient;
+
+static HASH: &str = "0ee0b0041380df22675472392ab54ff835b07b48";
+
+#[test]
+fn create_release_should_create_annotated_tag_in_bitbucket_cloud() {
+    let server = mock("POST", "/cloud/repositories/kamilczerw/vemo/refs/tags")
+        .match_header("Authorization", "Bearer secret")
+        .match_body(Matcher::Json(json!({
+            "name": "gateway/v1.3.0",
+            "target": { "hash": HASH },
+            "message": "## What's Changed"
+        })))
+        .with_status(201)
+        .create();
+
+    let api = format!("{}/cloud", mockito::server_url());
+    let repo = Repo::fixture(GitProvider::Bitbucket, "bitbucket.org", "kamilczerw/vemo");
+    let client = BitbucketClient::new("secret".to_string(), None, api, repo).unwrap();
+
+    let result = client.create_release("gateway - v1.3.0".to_string(), Tag::fixture("gateway", "1.3.0"), "## What's Changed".to_string(), HASH.to_string());
+
+    assert!(result.is_ok());
+    server.assert();
+}
+
+#[test]
+fn create_release_should_create_annotated_tag_in_bitbucket_server() {
+    let server = mock("POST", "/rest/api/1.0/projects/VEMO/repos/vemo/tags")
+        .match_header("Authorization", Matcher::Regex("^Basic ".to_string()))
+        .match_body(Matcher::Json(json!({
+            "name": "gateway/v1.3.0",
+            "startPoint": HASH,
+            "message": "## What's Changed"
+        })))
+        .with_status(200)
+        .create();
+
+    let api = format!("{}/rest/api/1.0", mockito::server_url());
+    let repo = Repo::fixture(GitProvider::BitbucketServer, "bitbucket.org", "VEMO/vemo");
+    let client = BitbucketClient::new("secret".to_string(), Some("kamil".to_string()), api, repo).unwrap();
+
+    let result = client.create_release("gateway - v1.3.0".to_string(), Tag::fixture("gateway", "1.3.0"), "## What's Changed".to_string(), HASH.to_string());
+
+    assert!(result.is_ok());
+    server.assert();
+}
+
+#[test]
+fn new_client_for_bitbucket_server_should_fail_when_project_is_missing() {
+    let repo = Repo::fixture(GitProvider::BitbucketServer, "bitbucket.org", "vemo");
+    let client = BitbucketClient::new("secret".to_string(), None, "http://localhost".to_string(), repo);
+
+    assert!(client.is_err());
+}
@@ -1,4 +1,6 @@
 use std::fmt::{Display, Formatter};
+use reqwest::blocking::Response;
+use serde_json::Value;
 use crate::commands::shell::git::GitProvider;
 
 #[derive(Debug)]
@@ -11,6 +13,65 @@ pub enum GitClientError {
 
     /// Git provider API request error
     RequestError(reqwest::Error),
+
+    /// Repository name cannot be used with the git provider API
+    InvalidRepo(String),
+
+    /// Git provider API responded with a non-successful status
+    ApiError {
+        status: u16,
+        message: String,
+        documentation_url: Option<String>
+    },
+}
+
+impl GitClientError {
+    /// Turn a non-successful response into an `ApiError`, with the message taken from the JSON
+    /// error body returned by the git provider.
+    pub fn check_status(response: Response) -> Result<Response, GitClientError> {
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response)
+        }
+
+        let text = response.text().unwrap_or_default();
+        let body: Value = serde_json::from_str(&text).unwrap_or(Value::Null);
+
+        let message = Self::error_message(&body)
+            .or_else(|| Some(text.trim().to_string()).filter(|t| !t.is_empty()))
+            .unwrap_or_else(|| status.canonical_reason().unwrap_or("Unknown error").to_string());
+        let documentation_url = body["documentation_url"].as_str().map(String::from);
+
+        Err(GitClientError::ApiError { status: status.as_u16(), message, documentation_url })
+    }
+
+    /// Extract the error message from the JSON error body of GitHub, GitLab, Gitea or Bitbucket
+    fn error_message(body: &Value) -> Option<String> {
+        let message = match &body["message"] {
+            Value::String(message) => Some(message.clone()),
+            Value::Null => None,
+            other => Some(other.to_string())
+        };
+        let message = message
+            .or_else(|| body["error"]["message"].as_str().map(String::from))
+            .or_else(|| body["error"].as_str().map(String::from))
+            .or_else(|| body["errors"][0]["message"].as_str().map(String::from))?;
+
+        // GitHub describes validation failures in a list of errors, e.g. an already existing tag
+        let details: Vec<String> = body["errors"].as_array().into_iter().flatten()
+            .filter_map(|e| match (e["code"].as_str(), e["field"].as_str()) {
+                (Some(code), Some(field)) => Some(format!("{}: {}", code, field)),
+                (Some(code), None) => Some(code.to_string()),
+                _ => None
+            })
+            .collect();
+
+        if details.is_empty() {
+            Some(message)
+        } else {
+            Some(format!("{} ({})", message, details.join(", ")))
+        }
+    }
 }
 
 impl Display for GitClientError {
@@ -27,6 +88,14 @@ impl Display for GitClientError {
             },
             GitClientError::UnsupportedProvider(provider) => write!(f, "Unsupported provider: {}", provider),
             GitClientError::RequestError(err) => write!(f, "Request error: {}", err),
+            GitClientError::InvalidRepo(repo) => write!(f, "Invalid repository name: {}", repo),
+            GitClientError::ApiError { status, message, documentation_url } => {
+                write!(f, "Git provider API responded with status {}: {}", status, message)?;
+                match documentation_url {
+                    Some(url) => write!(f, ", see {}", url),
+                    None => Ok(())
+                }
+            },
         }
     }
 }
@@ -0,0 +1,52 @@
+use reqwest::blocking::Client;
+use crate::git::client::error::GitClientError;
+use crate::git::GitClient;
+use crate::commands::shell::git::{Repo, Tag};
+use serde_json::json;
+
+/// Client for Gitea and Forgejo, which share the same releases API
+pub struct GiteaClient {
+    pub token: String,
+    pub http: Client,
+    pub api: String,
+    pub repo: String
+}
+
+impl GiteaClient {
+    /// Create a new GiteaClient
+    /// # Arguments
+    /// * `token` - Gitea token
+    /// * `api` - Base url of the Gitea API, e.g. https://git.corp.local/api/v1
+    /// * `repo` - Repo object
+    pub fn new(token: String, api: String, repo: Repo) -> Result<GiteaClient, GitClientError> {
+        Ok(GiteaClient {
+            token,
+            http: Client::new(),
+            api: api.trim_end_matches('/').to_string(),
+            repo: repo.repo_name
+        })
+    }
+}
+
+impl GitClient for GiteaClient {
+    /// Create a new Gitea release
+    fn create_release(&self, name: String, tag: Tag, description: String, target: String) -> Result<(), GitClientError> {
+        let body = json!({
+            "tag_name": tag.raw,
+            "target_commitish": target,
+            "name": name,
+            "body": description,
+        });
+
+        self.http.post(format!("{}/repos/{}/releases", self.api, self.repo))
+            .header("Authorization", format!("token {}", self.token))
+            .header("User-Agent", "Vemo-Cli")
+            .header("Content-Type", "application/json")
+            .body(serde_json::to_string(&body).unwrap())
+            .send()
+            .map_err(GitClientError::RequestError)
+            .and_then(GitClientError::check_status)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,31 @@
+use mockito::{mock, Matcher};
+use serde_json::json;
+use crate::commands::shell::git::{GitProvider, Repo, Tag};
+use crate::git::client::gitea::GiteaClient;
+use crate::git::GitClient;
+
+static HASH: &str = "0ee0b0041380df22675472392ab54ff835b07b48";
+
+#[test]
+fn create_release_should_post_release_to_gitea_api() {
+    let server = mock("POST", "/api/v1/repos/kamilczerw/vemo/releases")
+        .match_header("Authorization", "token secret")
+        .match_body(Matcher::Json(json!({
+            "tag_name": "gateway/v1.3.0",
+            "target_commitish": HASH,
+            "name": "gateway - v1.3.0",
+            "body": "## What's Changed"
+        })))
+        .with_status(201)
+        .create();
+
+    let api = format!("{}/api/v1", mockito::server_url());
+    let repo = Repo::fixture(GitProvider::Gitea, "git.corp.local", "kamilczerw/vemo");
+    let client = GiteaClient::new("secret".to_string(), api, repo).unwrap();
+    let tag = Tag::fixture("gateway", "1.3.0");
+
+    let result = client.create_release("gateway - v1.3.0".to_string(), tag, "## What's Changed".to_string(), HASH.to_string());
+
+    assert!(result.is_ok());
+    server.assert();
+}
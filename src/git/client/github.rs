@@ -16,12 +16,13 @@ impl GithubClient {
     /// Create a new GithubClient
     /// # Arguments
     /// * `token` - Github token
+    /// * `api` - Base url of the Github API, e.g. https://api.github.com
     /// * `repo` - Repo object
-    pub fn new(token: String, repo: Repo) -> Result<GithubClient, GitClientError> {
+    pub fn new(token: String, api: String, repo: Repo) -> Result<GithubClient, GitClientError> {
         Ok(GithubClient {
             token,
             http: Client::new(),
-            api: "https://api.github.com".to_string(),
+            api: api.trim_end_matches('/').to_string(),
             repo: repo.repo_name
         })
     }
@@ -29,20 +30,22 @@ impl GithubClient {
 
 impl GitClient for GithubClient {
     /// Create a new Github release
-    fn create_release(&self, name: String, tag: Tag, description: String) -> Result<(), GitClientError> {
+    fn create_release(&self, name: String, tag: Tag, description: String, target: String) -> Result<(), GitClientError> {
         let body = json!({
             "tag_name": tag.raw,
+            "target_commitish": target,
             "name": name,
             "body": description,
         });
 
-        self.http.post(&format!("{}/repos/{}/releases", self.api, self.repo))
+        self.http.post(format!("{}/repos/{}/releases", self.api, self.repo))
             .header("Authorization", format!("token {}", self.token))
             .header("User-Agent", "Vemo-Cli")
             .header("Content-Type", "application/json")
             .body(serde_json::to_string(&body).unwrap())
             .send()
-            .map_err(|e| GitClientError::RequestError(e))?;
+            .map_err(GitClientError::RequestError)
+            .and_then(GitClientError::check_status)?;
 
         Ok(())
     }
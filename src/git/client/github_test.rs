@@ -0,0 +1,74 @@
+use mockito::{mock, Matcher};
+use serde_json::json;
+use crate::commands::shell::git::{GitProvider, Repo, Tag};
+use crate::git::client::error::GitClientError;
+use crate::git::client::github::GithubClient;
+use crate::git::GitClient;
+
+static HASH: &str = "0ee0b0041380df22675472392ab54ff835b07b48";
+
+#[test]
+fn create_release_should_post_release_to_configured_api() {
+    let server = mock("POST", "/api/v3/repos/kamilczerw/vemo/releases")
+        .match_header("Authorization", "token secret")
+        .match_body(Matcher::Json(json!({
+            "tag_name": "gateway/v1.3.0",
+            "target_commitish": HASH,
+            "name": "gateway - v1.3.0",
+            "body": "## What's Changed"
+        })))
+        .with_status(201)
+        .create();
+
+    let api = format!("{}/api/v3", mockito::server_url());
+    let repo = Repo::fixture(GitProvider::Github, "github.mycompany.com", "kamilczerw/vemo");
+    let client = GithubClient::new("secret".to_string(), api, repo).unwrap();
+
+    let result = client.create_release("gateway - v1.3.0".to_string(), Tag::fixture("gateway", "1.3.0"), "## What's Changed".to_string(), HASH.to_string());
+
+    assert!(result.is_ok());
+    server.assert();
+}
+
+#[test]
+fn create_release_should_return_api_error_when_tag_already_exists() {
+    let _server = mock("POST", "/conflict/repos/kamilczerw/vemo/releases")
+        .with_status(422)
+        .with_body(r#"{
+            "message": "Validation Failed",
+            "errors": [{ "resource": "Release", "code": "already_exists", "field": "tag_name" }],
+            "documentation_url": "https://docs.github.com/rest/releases/releases#create-a-release"
+        }"#)
+        .create();
+
+    let api = format!("{}/conflict", mockito::server_url());
+    let repo = Repo::fixture(GitProvider::Github, "github.mycompany.com", "kamilczerw/vemo");
+    let client = GithubClient::new("secret".to_string(), api, repo).unwrap();
+
+    let result = client.create_release("gateway - v1.3.0".to_string(), Tag::fixture("gateway", "1.3.0"), "".to_string(), HASH.to_string());
+
+    match result {
+        Err(GitClientError::ApiError { status, message, documentation_url }) => {
+            assert_eq!(status, 422);
+            assert_eq!(message, "Validation Failed (already_exists: tag_name)");
+            assert_eq!(documentation_url, Some("https://docs.github.com/rest/releases/releases#create-a-release".to_string()));
+        }
+        _ => panic!("Expected ApiError, got {:?}", result)
+    }
+}
+
+#[test]
+fn create_release_should_return_api_error_when_token_is_invalid() {
+    let _server = mock("POST", "/unauthorized/repos/kamilczerw/vemo/releases")
+        .with_status(401)
+        .with_body(r#"{"message": "Bad credentials", "documentation_url": "https://docs.github.com/rest"}"#)
+        .create();
+
+    let api = format!("{}/unauthorized", mockito::server_url());
+    let repo = Repo::fixture(GitProvider::Github, "github.mycompany.com", "kamilczerw/vemo");
+    let client = GithubClient::new("invalid".to_string(), api, repo).unwrap();
+
+    let result = client.create_release("gateway - v1.3.0".to_string(), Tag::fixture("gateway", "1.3.0"), "".to_string(), HASH.to_string());
+
+    assert!(matches!(result, Err(GitClientError::ApiError { status: 401, .. })));
+}
@@ -0,0 +1,51 @@
+use reqwest::blocking::Client;
+use crate::git::client::error::GitClientError;
+use crate::git::GitClient;
+use crate::commands::shell::git::{Repo, Tag};
+use serde_json::json;
+
+pub struct GitlabClient {
+    pub token: String,
+    pub http: Client,
+    pub api: String,
+    pub project: String
+}
+
+impl GitlabClient {
+    /// Create a new GitlabClient
+    /// # Arguments
+    /// * `token` - Gitlab token
+    /// * `api` - Base url of the Gitlab API, e.g. https://gitlab.com/api/v4
+    /// * `repo` - Repo object
+    pub fn new(token: String, api: String, repo: Repo) -> Result<GitlabClient, GitClientError> {
+        Ok(GitlabClient {
+            token,
+            http: Client::new(),
+            api: api.trim_end_matches('/').to_string(),
+            project: urlencoding::encode(&repo.repo_name).into_owned()
+        })
+    }
+}
+
+impl GitClient for GitlabClient {
+    /// Create a new Gitlab release
+    fn create_release(&self, name: String, tag: Tag, description: String, target: String) -> Result<(), GitClientError> {
+        let body = json!({
+            "tag_name": tag.raw,
+            "ref": target,
+            "name": name,
+            "description": description,
+        });
+
+        self.http.post(format!("{}/projects/{}/releases", self.api, self.project))
+            .header("PRIVATE-TOKEN", &self.token)
+            .header("User-Agent", "Vemo-Cli")
+            .header("Content-Type", "application/json")
+            .body(serde_json::to_string(&body).unwrap())
+            .send()
+            .map_err(GitClientError::RequestError)
+            .and_then(GitClientError::check_status)?;
+
+        Ok(())
+    }
+}
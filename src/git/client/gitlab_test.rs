@@ -0,0 +1,53 @@
+use mockito::{mock, Matcher};
+use serde_json::json;
+use crate::commands::shell::git::{GitProvider, Repo, Tag};
+use crate::git::client::error::GitClientError;
+use crate::git::client::gitlab::GitlabClient;
+use crate::git::GitClient;
+
+static HASH: &str = "0ee0b0041380df22675472392ab54ff835b07b48";
+
+#[test]
+fn create_release_should_post_release_to_url_encoded_project() {
+    let server = mock("POST", "/api/v4/projects/kamilczerw%2Ftools%2Fvemo/releases")
+        .match_header("PRIVATE-TOKEN", "secret")
+        .match_body(Matcher::Json(json!({
+            "tag_name": "gateway/v1.3.0",
+            "ref": HASH,
+            "name": "gateway - v1.3.0",
+            "description": "## What's Changed"
+        })))
+        .with_status(201)
+        .create();
+
+    let api = format!("{}/api/v4/", mockito::server_url());
+    let repo = Repo::fixture(GitProvider::Gitlab, "gitlab.com", "kamilczerw/tools/vemo");
+    let client = GitlabClient::new("secret".to_string(), api, repo).unwrap();
+
+    let result = client.create_release("gateway - v1.3.0".to_string(), Tag::fixture("gateway", "1.3.0"), "## What's Changed".to_string(), HASH.to_string());
+
+    assert!(result.is_ok());
+    server.assert();
+}
+
+#[test]
+fn create_release_should_fail_when_api_responds_with_error() {
+    let _server = mock("POST", "/failing/projects/kamilczerw%2Ftools%2Fvemo/releases")
+        .with_status(409)
+        .with_body(r#"{"message":"Release already exists"}"#)
+        .create();
+
+    let api = format!("{}/failing", mockito::server_url());
+    let repo = Repo::fixture(GitProvider::Gitlab, "gitlab.com", "kamilczerw/tools/vemo");
+    let client = GitlabClient::new("secret".to_string(), api, repo).unwrap();
+
+    let result = client.create_release("gateway - v1.3.0".to_string(), Tag::fixture("gateway", "1.3.0"), "".to_string(), HASH.to_string());
+
+    match result {
+        Err(GitClientError::ApiError { status, message, .. }) => {
+            assert_eq!(status, 409);
+            assert_eq!(message, "Release already exists");
+        }
+        _ => panic!("Expected ApiError, got {:?}", result)
+    }
+}
@@ -4,19 +4,95 @@ use crate::git::client::error::GitClientError;
 use crate::git::GitClient;
 
 mod github;
+mod gitlab;
+mod bitbucket;
+mod gitea;
 pub mod error;
 
+#[cfg(test)]
+mod github_test;
+
+#[cfg(test)]
+mod gitlab_test;
+
+#[cfg(test)]
+mod bitbucket_test;
+
+#[cfg(test)]
+mod gitea_test;
+
 /// Create a new GitClient
+///
+/// Hosts listed in the `[providers]` config table use the configured provider type and API url
+/// instead of the ones detected from the remote url.
 pub fn new_client(config: &Config, repo: Repo) -> Result<Box<dyn GitClient>, GitClientError> {
+    let (provider, api_url) = match config.provider_config(&repo.host) {
+        Some(provider_config) => (provider_config.provider.clone(), provider_config.api_url.clone()),
+        None => (repo.provider.clone(), None)
+    };
+    let repo = Repo { provider, ..repo };
+
     match repo.provider {
-        GitProvider::Github => github_client(config, repo),
+        GitProvider::Github => github_client(config, api_url, repo),
+        GitProvider::Gitlab => gitlab_client(config, api_url, repo),
+        GitProvider::Bitbucket | GitProvider::BitbucketServer => bitbucket_client(config, api_url, repo),
+        GitProvider::Gitea => gitea_client(config, api_url, repo),
         _ => Err(GitClientError::UnsupportedProvider(repo.provider))
     }
 }
 
 /// Create a new GithubClient
-fn github_client(config: &Config, repo: Repo) -> Result<Box<dyn GitClient>, GitClientError> {
-    config.gh_token.clone().map(|token| {
-        github::GithubClient::new(token, repo).map(|client| Box::new(client) as Box<dyn GitClient>)
+///
+/// If the API url is not configured, public GitHub API is used for github.com
+/// and GitHub Enterprise Server API of the remote's host otherwise.
+fn github_client(config: &Config, api_url: Option<String>, repo: Repo) -> Result<Box<dyn GitClient>, GitClientError> {
+    let api = api_url.or_else(|| config.gh_api_url.clone()).unwrap_or_else(|| match repo.host.as_str() {
+        "github.com" => "https://api.github.com".to_string(),
+        host => format!("https://{}/api/v3", host)
+    });
+
+    config.github_token(&repo.host).map(|token| {
+        github::GithubClient::new(token, api, repo).map(|client| Box::new(client) as Box<dyn GitClient>)
     }).ok_or(GitClientError::MissingToken(GitProvider::Github))?
 }
+
+/// Create a new GitlabClient
+///
+/// If `gitlab.api_url` is not configured, the API of the remote's host is used,
+/// so self-hosted instances work out of the box.
+fn gitlab_client(config: &Config, api_url: Option<String>, repo: Repo) -> Result<Box<dyn GitClient>, GitClientError> {
+    let api = api_url.or_else(|| config.gl_api_url.clone())
+        .unwrap_or_else(|| format!("https://{}/api/v4", repo.host));
+
+    config.gl_token.clone().map(|token| {
+        gitlab::GitlabClient::new(token, api, repo).map(|client| Box::new(client) as Box<dyn GitClient>)
+    }).ok_or(GitClientError::MissingToken(GitProvider::Gitlab))?
+}
+
+/// Create a new BitbucketClient
+///
+/// If `bitbucket.api_url` is not configured, Bitbucket Cloud API is used for bitbucket.org
+/// and the REST API of the remote's host for Bitbucket Server.
+fn bitbucket_client(config: &Config, api_url: Option<String>, repo: Repo) -> Result<Box<dyn GitClient>, GitClientError> {
+    let api = api_url.or_else(|| config.bb_api_url.clone()).unwrap_or_else(|| match repo.provider {
+        GitProvider::BitbucketServer => format!("https://{}/rest/api/1.0", repo.host),
+        _ => "https://api.bitbucket.org/2.0".to_string()
+    });
+    let provider = repo.provider.clone();
+
+    config.bb_token.clone().map(|token| {
+        bitbucket::BitbucketClient::new(token, config.bb_username.clone(), api, repo)
+            .map(|client| Box::new(client) as Box<dyn GitClient>)
+    }).ok_or(GitClientError::MissingToken(provider))?
+}
+
+/// Create a new GiteaClient
+///
+/// If the API url is not configured in the `[providers]` table, the API of the remote's host is used.
+fn gitea_client(config: &Config, api_url: Option<String>, repo: Repo) -> Result<Box<dyn GitClient>, GitClientError> {
+    let api = api_url.unwrap_or_else(|| format!("https://{}/api/v1", repo.host));
+
+    config.gt_token.clone().map(|token| {
+        gitea::GiteaClient::new(token, api, repo).map(|client| Box::new(client) as Box<dyn GitClient>)
+    }).ok_or(GitClientError::MissingToken(GitProvider::Gitea))?
+}
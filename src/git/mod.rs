@@ -4,5 +4,14 @@ use crate::git::client::error::GitClientError;
 pub mod client;
 
 pub trait GitClient {
-    fn create_release(&self, name: String, tag: Tag, body: String) -> Result<(), GitClientError>;
+    /// Publish a release for a tag, `target` is the hash of the commit the tag points to
+    fn create_release(&self, name: String, tag: Tag, body: String, target: String) -> Result<(), GitClientError>;
+
+    /// Whether the provider creates the tag itself.
+    ///
+    /// If true, vemo does not create and push the tag with git, the release body is expected
+    /// to be stored in the tag created by the provider.
+    fn creates_tag(&self) -> bool {
+        false
+    }
 }
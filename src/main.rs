@@ -5,16 +5,24 @@ mod cfg;
 mod error;
 mod git;
 
+#[cfg(test)]
+mod error_test;
+
+use std::env;
 use std::process::exit;
 use clap::Parser;
+use colored::Colorize;
 use log4rs::append::console::ConsoleAppender;
 use log4rs::config::{Appender, Root};
 use cfg::Config;
-use error::AppError;
+use error::{AppError, EXIT_FAILURE};
 use log::{debug, LevelFilter};
 
 use commands::Commands;
 use crate::commands::shell::git::Git;
+use crate::commands::shell::FetchMode;
+use crate::git::GitClient;
+use crate::git::client::error::GitClientError;
 
 /// Manage your monorepo versions with ease.
 ///
@@ -24,13 +32,27 @@ use crate::commands::shell::git::Git;
 #[clap(author, version, about, long_about = None)]
 #[clap(propagate_version = true)]
 struct Cli {
+    /// Use local tags only, without fetching them from the remote
+    #[clap(long, global = true)]
+    offline: bool,
+
+    /// Remote used to detect the git provider, fetch and push tags, overrides the "remote" config
+    #[clap(long, global = true, value_name = "NAME")]
+    remote: Option<String>,
+
     #[clap(subcommand)]
     command: Commands,
 }
 
 fn app() -> Result<(), AppError> {
     let cli = Cli::parse();
-    let config = Config::init()?;
+    let mut config = Config::init()?;
+    if cli.offline {
+        config.fetch = FetchMode::Never;
+    }
+    if let Some(remote) = &cli.remote {
+        config.remote = remote.clone();
+    }
 
     let stdout = ConsoleAppender::builder().build();
     let log_config = log4rs::config::Config::builder()
@@ -46,24 +68,57 @@ fn app() -> Result<(), AppError> {
 
     debug!("Configuration: {:#?}", config);
 
-    let format = &config.format;
-    let git = Git::init(format.clone());
-
-    let repo_info = git.get_repo_info()?;
-
-    // TODO: if the git client is not supported, the program should not fail
-    //       There should still be possibility to create a tag without a git provider
-    let git_client = git::client::new_client(&config, repo_info)?;
+    // Git commands and paths in the config are relative to the root of the repo
+    env::set_current_dir(&config.root).map_err(|e| AppError {
+        message: format!("Failed to open the root of the repo {}, {}", config.root.display(), e),
+        code: EXIT_FAILURE
+    })?;
 
     let result: () = match &cli.command {
-        Commands::List {} => commands::list::run(config)?,
-        Commands::Bump { name, component } =>
-            commands::bump::run(config, name, component, git_client)?
+        Commands::List { output } => commands::list::run(config, output)?,
+        Commands::Changed { output } => commands::changed::run(config, output)?,
+        Commands::Changelog { name, from, to } => commands::changelog::run(config, name, from, to)?,
+        Commands::History { name, limit, output } => commands::history::run(config, name, limit, output)?,
+        Commands::Bump(args) => {
+            let git_client = git_client(&config);
+            commands::bump::run(config, args, git_client)?
+        }
+        Commands::Set(args) => {
+            let git_client = git_client(&config);
+            commands::set::run(config, args, git_client)?
+        }
     };
 
     Ok(result)
 }
 
+/// Create a client for the git provider of the repo, if there is one.
+///
+/// Without a client, vemo still creates and pushes the tag, but no release is published. That is expected
+/// without a remote or with an unknown provider, other failures (e.g. a missing token) are shown as a warning.
+fn git_client(config: &Config) -> Option<Box<dyn GitClient>> {
+    let repo = match Git::init(config).and_then(|git| git.get_repo_info()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            debug!("Git provider of the repo not found, only a tag will be created. Reason: {:?}", e);
+            return None
+        }
+    };
+
+    match git::client::new_client(config, repo) {
+        Ok(client) => Some(client),
+        Err(GitClientError::UnsupportedProvider(provider)) => {
+            debug!("Git provider {} is not supported, only a tag will be created", provider);
+            None
+        }
+        Err(e) => {
+            println!("{} {}", "warning:".yellow().bold(), e);
+            println!("{} releases will not be published, only tags will be created", "warning:".yellow().bold());
+            None
+        }
+    }
+}
+
 fn main() {
     app().unwrap_or_else(|error| {
         println!("Error: {}", error.message);